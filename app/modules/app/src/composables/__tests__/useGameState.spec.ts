@@ -44,13 +44,22 @@ function makeGameStatus(overrides: Partial<GameStatus> = {}): GameStatus {
   return {
     installed: true,
     game_version: 100,
+    bundle_version: null,
     mod_available: true,
+    mod_version: null,
     mod_installable: true,
     mod_deployed: true,
+    entitlements: {},
+    entitlements_error: null,
     mod_outdated: false,
     mod_removable: false,
     game_running: false,
+    game_arch: null,
     launcher_running: false,
+    installations: [],
+    install_dir: null,
+    can_patch_now: false,
+    needs_restart_to_patch: false,
     ...overrides,
   };
 }
@@ -487,6 +496,18 @@ describe('useGameState', () => {
 
         expect(state.actionError.value).toContain('launch failed');
       });
+
+      it('uses the message of a CommandError', async () => {
+        mockInvoke.mockRejectedValue({kind: 'precondition', message: 'Missing entitlements'});
+
+        const state = useGameState();
+        state.launchGame();
+        await vi.waitFor(() => {
+          expect(state.actionPending.value).toBe(false);
+        });
+
+        expect(state.actionError.value).toBe('Missing entitlements');
+      });
     });
   });
 
@@ -1,3 +1,4 @@
+import type {CommandError} from '@generated/CommandError';
 import type {GameStatus} from '@generated/GameStatus';
 import type {ProcessStatus} from '@generated/ProcessStatus';
 import type {UpdateCheck} from '@generated/UpdateCheck';
@@ -10,16 +11,40 @@ import {computed, ref} from 'vue';
 
 const log = getLogger('App');
 
+/**
+ * Extract a displayable message from a rejected command.
+ *
+ * Commands returning a {@link CommandError} reject with a `{kind, message}` object, older ones with a plain string.
+ *
+ * @param err - the rejection value
+ * @returns the error message
+ */
+function errorMessage(err: unknown): string {
+  if (typeof err === 'object' && err !== null && 'message' in err) {
+    return String((err as CommandError).message);
+  }
+  return String(err);
+}
+
 const DEFAULT_GAME_STATUS: GameStatus = {
   installed: false,
   game_version: null,
+  bundle_version: null,
   mod_available: false,
+  mod_version: null,
   mod_installable: false,
   mod_deployed: false,
+  entitlements: {},
+  entitlements_error: null,
   mod_outdated: false,
   mod_removable: false,
   game_running: false,
+  game_arch: null,
   launcher_running: false,
+  installations: [],
+  install_dir: null,
+  can_patch_now: false,
+  needs_restart_to_patch: false,
 };
 
 // ---- Public Interface -----------------------------------------------------------
@@ -184,7 +209,7 @@ export function useGameState(): GameState {
     invoke<T>(command)
       .then(onSuccess)
       .catch((err) => {
-        actionError.value = String(err);
+        actionError.value = errorMessage(err);
       })
       .finally(() => {
         actionPending.value = false;
@@ -6,9 +6,13 @@ use serde_json::Value;
 /// Path to the root package.json (relative to the backend crate directory).
 const ROOT_PACKAGE_JSON: &str = "../../../package.json";
 
+/// Environment variable that turns a version mismatch into a build failure when set to `1` (e.g. in CI).
+const STRICT_VERSION_ENV: &str = "DAYSTROM_STRICT_VERSION";
+
 fn main() {
     // Ensure Cargo recompiles when the root package.json changes (version source of truth).
     println!("cargo:rerun-if-changed={ROOT_PACKAGE_JSON}");
+    println!("cargo:rerun-if-env-changed={STRICT_VERSION_ENV}");
 
     // Read the Tauri identifier from tauri.conf.json and expose it as a compile-time env var.
     // This avoids hardcoding the identifier in Rust source files.
@@ -20,13 +24,17 @@ fn main() {
         }
     }
 
-    check_version_sync();
+    let synced = check_version_sync();
+    println!("cargo:rustc-env=DAYSTROM_VERSION_SYNCED={synced}");
 
     tauri_build::build();
 }
 
 /// Warn at build time if the Cargo.toml version drifts from the root package.json.
-fn check_version_sync() {
+///
+/// With [`STRICT_VERSION_ENV`] set to `1`, the mismatch fails the build instead. Returns `false` on a mismatch, and
+/// `true` if the versions match or package.json cannot be read.
+fn check_version_sync() -> bool {
     let cargo_version = env::var("CARGO_PKG_VERSION").unwrap_or_default();
 
     let pkg_version = fs::read_to_string(ROOT_PACKAGE_JSON)
@@ -36,10 +44,16 @@ fn check_version_sync() {
 
     if let Some(pkg_version) = pkg_version {
         if cargo_version != pkg_version {
-            println!(
-                "cargo:warning=Version mismatch: Cargo.toml has {cargo_version}, \
+            let message = format!(
+                "Version mismatch: Cargo.toml has {cargo_version}, \
                  root package.json has {pkg_version} — please update Cargo.toml"
             );
+            if env::var(STRICT_VERSION_ENV).as_deref() == Ok("1") {
+                panic!("{message}");
+            }
+            println!("cargo:warning={message}");
+            return false;
         }
     }
+    true
 }
@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::thread;
 
 use serde::Serialize;
@@ -6,7 +9,10 @@ use tauri::{Emitter, Manager};
 use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
 use ts_rs::TS;
 
+use crate::diagnostics;
 use crate::game;
+use crate::game::entitlements::EntitlementState;
+use crate::logging;
 use crate::use_log;
 
 use_log!("Commands");
@@ -19,61 +25,243 @@ pub struct GameStatus {
     pub installed: bool,
     /// Installed game version from the `.version` file, if available.
     pub game_version: Option<u32>,
+    /// Marketing version from the app bundle's `Info.plist` (macOS only), if available.
+    pub bundle_version: Option<String>,
     /// Whether the mod library was found in the app's resource directory.
     pub mod_available: bool,
+    /// Version of the mod library from its `version.txt`, if available.
+    pub mod_version: Option<String>,
     /// Whether the mod can be installed or updated (game found and mod library bundled).
     pub mod_installable: bool,
     /// Whether the mod is deployed and ready (macOS: entitlements OK, Windows: DLL up to date).
     pub mod_deployed: bool,
+    /// State of each required entitlement, keyed by entitlement name. Always empty on Windows.
+    pub entitlements: BTreeMap<String, EntitlementState>,
+    /// Why the entitlements could not be checked (e.g. `codesign` unavailable), or `None` if the check ran.
+    /// When set, `entitlements` is empty and says nothing about the actual signature.
+    pub entitlements_error: Option<String>,
     /// Whether the mod DLL exists but is outdated (hash mismatch). Always `false` on macOS.
     pub mod_outdated: bool,
     /// Whether the mod can be removed from disk (Windows: DLL deployed or outdated, macOS: always false).
     pub mod_removable: bool,
     /// Whether the game process is currently running.
     pub game_running: bool,
+    /// How the running game executes, `native` or `rosetta` (macOS only); `None` if not running or unknown.
+    pub game_arch: Option<String>,
     /// Whether the Scopely launcher is currently running.
     pub launcher_running: bool,
+    /// Install directories of all detected installations, if there is more than one to choose from.
+    pub installations: Vec<String>,
+    /// Install directory of the installation this status refers to.
+    pub install_dir: Option<String>,
+    /// Whether [`prepare_mod`] can run right now: the game is installed and not running, on a platform that supports
+    /// patching (macOS and Windows).
+    pub can_patch_now: bool,
+    /// Whether the mod still needs preparing but the game has to be quit first, so the UI can say so.
+    pub needs_restart_to_patch: bool,
+}
+
+/// Category of a [`CommandError`], so the frontend can react without parsing the message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum CommandErrorKind {
+    /// STFC is not installed or could not be detected.
+    NotFound,
+    /// The action is not possible while the game is running.
+    GameRunning,
+    /// A precondition is not met (e.g. mod library not bundled, entitlements not patched).
+    Precondition,
+    /// The action itself failed; details are in the message and the log.
+    Failed,
+}
+
+/// Typed error returned by commands, serialised as `{ kind, message }`.
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(export)]
+pub struct CommandError {
+    /// Error category.
+    pub kind: CommandErrorKind,
+    /// Human-readable description, suitable for display.
+    pub message: String,
+}
+
+impl CommandError {
+    fn new(kind: CommandErrorKind, message: impl Into<String>) -> Self {
+        CommandError { kind, message: message.into() }
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::new(CommandErrorKind::Failed, message)
+    }
+}
+
+impl From<game::GameError> for CommandError {
+    fn from(error: game::GameError) -> Self {
+        let kind = match error {
+            game::GameError::NotInstalled => CommandErrorKind::NotFound,
+            game::GameError::GameRunning => CommandErrorKind::GameRunning,
+            game::GameError::MissingEntitlements(_) | game::GameError::ModNotFound => CommandErrorKind::Precondition,
+            game::GameError::Codesign(_) | game::GameError::Failed(_) => CommandErrorKind::Failed,
+        };
+        CommandError::new(kind, error.to_string())
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::new(CommandErrorKind::Failed, message)
+    }
 }
 
 /// Detect the STFC installation and check its entitlements, mod availability, and running state.
 #[tauri::command]
 pub fn get_game_status(app: tauri::AppHandle) -> GameStatus {
-    let mod_library = game::find_mod_library(&app);
+    let result = game_status_only(&app);
+
+    // Kick off an async update check if the game is installed
+    if result.installed {
+        thread::spawn(move || {
+            match check_for_update() {
+                Ok(check) => { let _ = app.emit("update-check", check); }
+                Err(_) => { let _ = app.emit("update-check-failed", ()); }
+            }
+        });
+    }
+
+    result
+}
+
+/// Like [`get_game_status`], but without kicking off an update check, for refreshes that do not need one.
+pub(crate) fn game_status_only(app: &tauri::AppHandle) -> GameStatus {
+    game_status(&SystemProbe(app))
+}
+
+/// The system lookups [`get_game_status`] depends on, so the status assembly can be tested with fakes.
+trait StatusProbe {
+    /// Path of the bundled mod library, see [`game::find_mod_library`].
+    fn mod_library(&self) -> Option<PathBuf>;
+    /// Version of the mod library, see [`game::mod_version`].
+    fn mod_version(&self, library: &Path) -> Option<String>;
+    /// Whether the Scopely launcher is running, see [`game::is_launcher_running`].
+    fn launcher_running(&self) -> bool;
+    /// All detected installations, see [`game::detect_all`].
+    fn detect_all(&self) -> Vec<game::GameInfo>;
+    /// The selected installation, see [`game::detect`].
+    fn detect(&self) -> Option<game::GameInfo>;
+    /// Entitlements of the game executable, see [`game::entitlements::check`].
+    fn check_entitlements(&self, executable: &Path) -> Result<game::entitlements::EntitlementStatus, String>;
+    /// Whether the game is running, see [`game::is_running`].
+    fn is_running(&self, executable: &Path) -> bool;
+    /// How the running game executes, see [`game::game_arch`].
+    fn game_arch(&self, executable: &Path) -> Option<String>;
+    /// Whether the mod DLL is deployed, see [`game::check_mod_deployment`].
+    #[cfg(target_os = "windows")]
+    fn mod_deployment(&self, install_dir: &Path, mod_library: &Path) -> game::ModDeploymentState;
+}
+
+/// The real [`StatusProbe`], backed by the filesystem and process list.
+struct SystemProbe<'a>(&'a tauri::AppHandle);
+
+impl StatusProbe for SystemProbe<'_> {
+    fn mod_library(&self) -> Option<PathBuf> {
+        game::find_mod_library(self.0)
+    }
+
+    fn mod_version(&self, library: &Path) -> Option<String> {
+        game::mod_version(library)
+    }
+
+    fn launcher_running(&self) -> bool {
+        game::is_launcher_running()
+    }
+
+    fn detect_all(&self) -> Vec<game::GameInfo> {
+        game::detect_all()
+    }
+
+    fn detect(&self) -> Option<game::GameInfo> {
+        game::detect()
+    }
+
+    fn check_entitlements(&self, executable: &Path) -> Result<game::entitlements::EntitlementStatus, String> {
+        game::entitlements::check(executable)
+    }
+
+    fn is_running(&self, executable: &Path) -> bool {
+        game::is_running(executable)
+    }
+
+    fn game_arch(&self, executable: &Path) -> Option<String> {
+        game::game_arch(executable)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn mod_deployment(&self, install_dir: &Path, mod_library: &Path) -> game::ModDeploymentState {
+        game::check_mod_deployment(install_dir, mod_library)
+    }
+}
+
+/// Assemble the [`GameStatus`] from the lookups of `probe`; core of [`get_game_status`].
+fn game_status(probe: &impl StatusProbe) -> GameStatus {
+    let mod_library = probe.mod_library();
     let mod_available = mod_library.is_some();
+    let mod_version = mod_library.as_deref().and_then(|library| probe.mod_version(library));
 
     match &mod_library {
         Some(path) => log_info!("Mod library found: {}", path.display()),
         None => log_warn!("Mod library not bundled, run pnpm build:mod"),
     }
 
-    let launcher_running = game::is_launcher_running();
+    let launcher_running = probe.launcher_running();
 
-    let result = match game::detect() {
+    let all_installs = probe.detect_all();
+    if all_installs.len() > 1 {
+        log_info!("{} STFC installations found", all_installs.len());
+    }
+    let installations: Vec<String> = match all_installs.len() {
+        0 | 1 => Vec::new(),
+        _ => all_installs.iter().map(|info| info.install_dir.display().to_string()).collect(),
+    };
+
+    match probe.detect() {
         Some(info) => {
             match info.installed_version {
                 Some(v) => log_info!("STFC found (v{v}): {}", info.executable.display()),
                 None => log_info!("STFC found: {}", info.executable.display()),
             }
 
-            let status = game::entitlements::check(&info.executable);
-            if status.all_granted() {
-                log_info!("Entitlements OK, mod injection ready");
-            } else {
-                let names: Vec<_> = status.missing.iter()
-                    .map(|k| k.strip_prefix("com.apple.security.").unwrap_or(k))
-                    .collect();
-                log_warn!("Missing entitlements: {}", names.join(", "));
+            let (status, entitlements_error) = match probe.check_entitlements(&info.executable) {
+                Ok(status) => (Some(status), None),
+                Err(e) => {
+                    log_warn!("Entitlements could not be checked: {e}");
+                    (None, Some(e))
+                }
+            };
+            match &status {
+                Some(status) if status.all_granted() => log_info!("Entitlements OK, mod injection ready"),
+                Some(status) => {
+                    log_warn!("Missing entitlements: {}", status.missing_short().join(", "));
+                    log_debug!("Granted entitlements: {}", status.granted_short().join(", "));
+                }
+                None => {}
             }
 
-            let game_running = game::is_running(&info.executable);
+            let game_running = probe.is_running(&info.executable);
+            let game_arch = if game_running { probe.game_arch(&info.executable) } else { None };
+            if let Some(arch) = &game_arch {
+                log_debug!("Game process is running {arch}");
+            }
 
             // macOS: mod is "deployed" when entitlements are OK (injection via DYLD)
             // Windows: mod is deployed when the DLL is copied and up to date
             #[cfg(target_os = "macos")]
-            let (mod_deployed, mod_outdated) = (status.all_granted(), false);
+            let (mod_deployed, mod_outdated) = (status.as_ref().is_some_and(|s| s.all_granted()), false);
             #[cfg(target_os = "windows")]
             let (mod_deployed, mod_outdated) = mod_library.as_ref().map(|lib| {
-                match game::check_mod_deployment(&info.install_dir, lib) {
+                match probe.mod_deployment(&info.install_dir, lib) {
                     game::ModDeploymentState::UpToDate => (true, false),
                     game::ModDeploymentState::Outdated => (false, true),
                     game::ModDeploymentState::NotDeployed => (false, false),
@@ -90,16 +278,33 @@ pub fn get_game_status(app: tauri::AppHandle) -> GameStatus {
             #[cfg(not(any(target_os = "macos", target_os = "windows")))]
             let mod_removable = false;
 
+            // Patching is not supported elsewhere, so it can never run and there is nothing to wait for
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            let (can_patch_now, needs_restart_to_patch) = (!game_running, game_running && !mod_deployed);
+            #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+            let (can_patch_now, needs_restart_to_patch) = (false, false);
+
             GameStatus {
                 installed: true,
                 game_version: info.installed_version,
+                bundle_version: info.bundle_version.clone(),
                 mod_available,
+                mod_version,
                 mod_installable: mod_available,
                 mod_deployed,
+                entitlements: status
+                    .map(|s| s.states.iter().map(|(key, state)| (key.to_string(), *state)).collect())
+                    .unwrap_or_default(),
+                entitlements_error,
                 mod_outdated,
                 mod_removable,
                 game_running,
+                game_arch,
                 launcher_running,
+                installations,
+                install_dir: Some(info.install_dir.display().to_string()),
+                can_patch_now,
+                needs_restart_to_patch,
             }
         }
         None => {
@@ -107,54 +312,357 @@ pub fn get_game_status(app: tauri::AppHandle) -> GameStatus {
             GameStatus {
                 installed: false,
                 game_version: None,
+                bundle_version: None,
                 mod_available,
+                mod_version,
                 mod_installable: false,
                 mod_deployed: false,
+                entitlements: BTreeMap::new(),
+                entitlements_error: None,
                 mod_outdated: false,
                 mod_removable: false,
                 game_running: false,
+                game_arch: None,
                 launcher_running,
+                installations,
+                install_dir: None,
+                can_patch_now: false,
+                needs_restart_to_patch: false,
             }
         }
+    }
+}
+
+/// Event emitted by [`prepare_mod`] on macOS as each entitlement patching step starts.
+///
+/// The payload is a `PatchStage` string (e.g. `"running_codesign"`); the final result comes from the command itself.
+#[cfg(target_os = "macos")]
+pub const PATCH_PROGRESS: &str = "patch-progress";
+
+/// Prepare the mod for use: patch entitlements on macOS, deploy the DLL on Windows.
+///
+/// On macOS, progress is reported via `patch-progress` events, and the executable is signed with the optional code
+/// signing `identity` (see [`patch_entitlements`]).
+/// Returns the refreshed game status so the frontend can update in one step.
+#[tauri::command]
+pub fn prepare_mod(app: tauri::AppHandle, identity: Option<String>) -> Result<GameStatus, CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+    prepare_install(app, &info.install_dir, &info.executable, identity.as_deref())
+}
+
+/// Like [`prepare_mod`], but for an explicitly chosen executable instead of the detected installation.
+///
+/// Lets users with several installs, or a copy of the game, prepare one that detection would not pick.
+/// The path must exist and be the STFC executable for this platform. Takes the same optional `identity`.
+#[tauri::command]
+pub fn prepare_mod_at(
+    app: tauri::AppHandle,
+    executable: String,
+    identity: Option<String>,
+) -> Result<GameStatus, CommandError> {
+    let (install_dir, executable) = game::install_for_executable(Path::new(&executable))?;
+    prepare_install(app, &install_dir, &executable, identity.as_deref())
+}
+
+/// Shared body of [`prepare_mod`] and [`prepare_mod_at`].
+#[allow(unused_variables)] // `install_dir` is only used on Windows, `identity` only on macOS
+fn prepare_install(
+    app: tauri::AppHandle,
+    install_dir: &Path,
+    executable: &Path,
+    identity: Option<&str>,
+) -> Result<GameStatus, CommandError> {
+    if game::is_running(executable) {
+        return Err(CommandError::new(
+            CommandErrorKind::GameRunning,
+            "Cannot prepare mod while the game is running",
+        ));
+    }
+
+    #[cfg(target_os = "macos")]
+    patch_entitlements(&app, executable, identity)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let mod_library = game::find_mod_library(&app).ok_or(game::GameError::ModNotFound)?;
+        game::deploy_mod(install_dir, &mod_library)?;
+    }
+
+    game::invalidate_detection();
+    Ok(get_game_status(app))
+}
+
+/// Re-sign `executable` with the mod entitlements, reporting progress via `patch-progress` events.
+///
+/// Signs with the code signing `identity` if given, else with [`game::entitlements::SIGN_IDENTITY_ENV`] if set, and
+/// ad hoc otherwise. Uses the plist from [`game::entitlements::ENTITLEMENTS_FILE_ENV`] if set.
+#[cfg(target_os = "macos")]
+fn patch_entitlements(
+    app: &tauri::AppHandle,
+    executable: &Path,
+    identity: Option<&str>,
+) -> Result<(), game::GameError> {
+    let progress = |stage: game::entitlements::PatchStage| {
+        let _ = app.emit(PATCH_PROGRESS, stage);
+    };
+    let identity = identity
+        .map(str::to_string)
+        .or_else(|| std::env::var(game::entitlements::SIGN_IDENTITY_ENV).ok())
+        .filter(|s| !s.trim().is_empty());
+    let entitlements_file = std::env::var_os(game::entitlements::ENTITLEMENTS_FILE_ENV)
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from);
+    game::entitlements::patch(executable, &[], entitlements_file.as_deref(), identity.as_deref(), Some(&progress))
+}
+
+/// Preview the entitlement changes [`prepare_mod`] would make, without re-signing the executable (macOS only).
+#[tauri::command]
+pub fn preview_patch() -> Result<game::entitlements::PatchPlan, CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+    Ok(game::entitlements::plan(&info.executable, &[])?)
+}
+
+/// Outcome of one precondition checked by [`readiness`].
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ReadinessCheck {
+    /// Whether the precondition is met.
+    pub ok: bool,
+    /// Human-readable explanation, suitable for display.
+    pub message: String,
+}
+
+impl ReadinessCheck {
+    fn new(ok: bool, message: impl Into<String>) -> Self {
+        ReadinessCheck { ok, message: message.into() }
+    }
+}
+
+/// Every precondition for launching the game with the mod, as returned by [`readiness`].
+#[derive(Clone, Debug, Serialize, TS)]
+#[ts(export)]
+pub struct Readiness {
+    /// STFC was found on this machine.
+    pub game_installed: ReadinessCheck,
+    /// The required entitlements are granted (macOS only; always passes elsewhere).
+    pub entitlements_ok: ReadinessCheck,
+    /// `codesign` can be run (macOS only; always passes elsewhere).
+    pub codesign_available: ReadinessCheck,
+    /// The mod library is bundled or overridden via `DAYSTROM_MOD_LIBRARY`.
+    pub mod_bundled: ReadinessCheck,
+    /// The game is not already running.
+    pub game_not_running: ReadinessCheck,
+    /// Whether all of the above pass, so [`launch_game`] is expected to succeed.
+    pub ready: bool,
+}
+
+/// Check every precondition for [`launch_game`] in one go, each with a message the UI can show as is.
+///
+/// Unlike [`get_game_status`], this answers only "can we launch?" and explains each failing gate.
+#[tauri::command]
+pub fn readiness(app: tauri::AppHandle) -> Readiness {
+    let info = game::detect();
+
+    let game_installed = match &info {
+        Some(info) => ReadinessCheck::new(true, format!("STFC found at {}", info.install_dir.display())),
+        None => ReadinessCheck::new(false, game::GameError::NotInstalled.to_string()),
+    };
+
+    let game_not_running = match &info {
+        Some(info) if game::is_running(&info.executable) => {
+            ReadinessCheck::new(false, game::GameError::GameRunning.to_string())
+        }
+        Some(_) => ReadinessCheck::new(true, "The game is not running"),
+        None => ReadinessCheck::new(false, "No game to check"),
     };
 
-    // Kick off an async update check if the game is installed
-    if result.installed {
-        thread::spawn(move || {
-            match check_for_update() {
-                Ok(check) => { let _ = app.emit("update-check", check); }
-                Err(_) => { let _ = app.emit("update-check-failed", ()); }
+    let mod_bundled = match game::find_mod_library(&app) {
+        Some(library) => ReadinessCheck::new(true, format!("Mod library found at {}", library.display())),
+        None => ReadinessCheck::new(false, game::GameError::ModNotFound.to_string()),
+    };
+
+    #[cfg(target_os = "macos")]
+    let (codesign_available, entitlements_ok) = {
+        let codesign_available = if game::entitlements::prerequisites().codesign_available {
+            ReadinessCheck::new(true, "codesign is available")
+        } else {
+            ReadinessCheck::new(false, "codesign is not available, install the Xcode command line tools")
+        };
+        let entitlements_ok = match &info {
+            None => ReadinessCheck::new(false, "No game to check"),
+            Some(_) if !codesign_available.ok => {
+                ReadinessCheck::new(false, "Cannot check entitlements without codesign")
             }
-        });
-    }
+            Some(info) => match game::entitlements::check(&info.executable) {
+                Ok(status) if status.all_granted() => {
+                    ReadinessCheck::new(true, "All required entitlements are granted")
+                }
+                Ok(status) => {
+                    let names = status.missing_short().into_iter().map(String::from).collect();
+                    ReadinessCheck::new(false, game::GameError::MissingEntitlements(names).to_string())
+                }
+                Err(e) => ReadinessCheck::new(false, format!("Could not check entitlements: {e}")),
+            },
+        };
+        (codesign_available, entitlements_ok)
+    };
+    #[cfg(not(target_os = "macos"))]
+    let (codesign_available, entitlements_ok) = (
+        ReadinessCheck::new(true, "Not required on this platform"),
+        ReadinessCheck::new(true, "Not required on this platform"),
+    );
 
-    result
+    let ready = [&game_installed, &entitlements_ok, &codesign_available, &mod_bundled, &game_not_running]
+        .iter()
+        .all(|check| check.ok);
+    log_debug!("Readiness: {}", if ready { "ready to launch" } else { "not ready" });
+
+    Readiness { game_installed, entitlements_ok, codesign_available, mod_bundled, game_not_running, ready }
 }
 
-/// Prepare the mod for use: patch entitlements on macOS, deploy the DLL on Windows.
+/// Return the raw entitlements plist of the game executable as dumped by `codesign -d` (macOS only), for support
+/// to copy from a player's machine. An unsigned executable or one without entitlements yields an empty string.
+#[tauri::command]
+pub fn get_entitlements_xml() -> Result<String, CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+    Ok(game::entitlements::read_entitlements_xml(&info.executable)?)
+}
+
+/// Report whether `codesign` and the Xcode command line tools are available (macOS only; nothing is on other
+/// platforms), so the UI can prompt for their installation before [`prepare_mod`] fails.
+#[tauri::command]
+pub fn check_prerequisites() -> game::entitlements::Prerequisites {
+    game::entitlements::prerequisites()
+}
+
+/// Check whether [`prepare_mod`] can patch the game executable: it must be writable, outside protected locations,
+/// and `codesign` must be available (macOS only; never ready on other platforms).
+#[tauri::command]
+pub fn can_patch() -> Result<game::entitlements::PatchReadiness, CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+    Ok(game::entitlements::patch_readiness(&info.executable))
+}
+
+/// Restore the entitlements the game executable had before the first [`prepare_mod`] (macOS only).
 ///
 /// Returns the refreshed game status so the frontend can update in one step.
 #[tauri::command]
-pub fn prepare_mod(app: tauri::AppHandle) -> Result<GameStatus, String> {
-    let info = game::detect().ok_or("STFC not found")?;
+pub fn restore_entitlements(app: tauri::AppHandle) -> Result<GameStatus, CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
 
     if game::is_running(&info.executable) {
-        return Err("Cannot prepare mod while the game is running".to_string());
+        return Err(CommandError::new(
+            CommandErrorKind::GameRunning,
+            "Cannot restore entitlements while the game is running",
+        ));
     }
 
+    game::entitlements::restore(&info.executable)?;
+    game::invalidate_detection();
+    Ok(get_game_status(app))
+}
+
+/// What [`uninstall_mod`] reverted, so the frontend can tell the player what changed.
+#[derive(Clone, Debug, Default, Serialize, TS)]
+#[ts(export)]
+pub struct UninstallSummary {
+    /// Whether the original entitlements were restored from the backup (macOS only).
+    pub entitlements_restored: bool,
+    /// Path of the mod library that was removed (macOS: the injected dylib, Windows: the deployed DLL), if any.
+    pub library_removed: Option<String>,
+    /// Steps that were not performed, with the reason.
+    pub skipped: Vec<String>,
+}
+
+/// Fully revert the mod installation without asking for confirmation.
+///
+/// On macOS, restores the original entitlements if a backup exists and removes the bundled dylib if it is a writable
+/// copy outside any `.app` bundle. A library inside Project Daystrom's own signed bundle, or one set via
+/// `DAYSTROM_MOD_LIBRARY`, is never removed and reported as skipped. On Windows, removes the deployed DLL and its
+/// runtime artefacts. Refuses while the game is running. Each step is logged under the `Cleanup` logger.
+#[tauri::command]
+#[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+pub fn uninstall_mod(app: tauri::AppHandle) -> Result<UninstallSummary, CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+
+    if game::is_running(&info.executable) {
+        return Err(CommandError::new(
+            CommandErrorKind::GameRunning,
+            "Cannot uninstall the mod while the game is running",
+        ));
+    }
+
+    log_info_as!("Cleanup", "Uninstalling mod from {}", info.install_dir.display());
+    let mut summary = UninstallSummary::default();
+
     #[cfg(target_os = "macos")]
     {
-        game::entitlements::patch(&info.executable)?;
+        if game::entitlements::has_backup(&info.executable) {
+            log_info_as!("Cleanup", "Restoring original entitlements");
+            game::entitlements::restore(&info.executable)?;
+            summary.entitlements_restored = true;
+        } else {
+            log_info_as!("Cleanup", "No entitlements backup, signature left as is");
+            summary.skipped.push("No entitlements backup found".to_string());
+        }
+
+        // A developer's own build is not ours to delete, only the library bundled with the app
+        if let Some(library) = game::overridden_mod_library() {
+            log_info_as!("Cleanup", "Mod library override {} left in place", library.display());
+            summary.skipped.push(format!("Mod library override is not removed: {}", library.display()));
+        }
+        match game::bundled_mod_library(&app) {
+            // Deleting from our own bundle would break its sealed signature, and the mod with it until a reinstall
+            Some(library) if game::app_bundle(&library).is_some() => {
+                log_info_as!("Cleanup", "Mod library {} is part of the app bundle, left in place", library.display());
+                summary.skipped.push(format!("Mod library is part of the signed app bundle: {}", library.display()));
+            }
+            Some(library) => match std::fs::remove_file(&library) {
+                Ok(()) => {
+                    log_info_as!("Cleanup", "Removed mod library {}", library.display());
+                    summary.library_removed = Some(library.display().to_string());
+                }
+                Err(e) if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::ReadOnlyFilesystem
+                ) => {
+                    log_info_as!("Cleanup", "Mod library {} is not writable, left in place", library.display());
+                    summary.skipped.push(format!("Mod library is read-only: {}", library.display()));
+                }
+                Err(e) => {
+                    log_warn_as!("Cleanup", "Could not remove mod library {}: {e}", library.display());
+                    summary.skipped.push(format!("Could not remove mod library: {e}"));
+                }
+            },
+            None => {
+                log_info_as!("Cleanup", "No bundled mod library found, nothing to remove");
+                summary.skipped.push("No bundled mod library found".to_string());
+            }
+        }
     }
 
     #[cfg(target_os = "windows")]
     {
-        let mod_library = game::find_mod_library(&app)
-            .ok_or("Mod library not found — run build:mod first")?;
-        game::deploy_mod(&info.install_dir, &mod_library)?;
+        let dll = info.install_dir.join("version.dll");
+        if dll.exists() {
+            log_info_as!("Cleanup", "Removing deployed mod {}", dll.display());
+            game::remove_mod(&info.install_dir)?;
+            summary.library_removed = Some(dll.display().to_string());
+        } else {
+            log_info_as!("Cleanup", "No deployed mod in {}", info.install_dir.display());
+            summary.skipped.push("Mod is not deployed".to_string());
+        }
     }
 
-    Ok(get_game_status(app))
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        log_info_as!("Cleanup", "Mod is never installed on this platform, nothing to revert");
+        summary.skipped.push("Mod is not supported on this platform".to_string());
+    }
+
+    game::invalidate_detection();
+    log_info_as!("Cleanup", "Mod uninstalled");
+    Ok(summary)
 }
 
 /// Remove the deployed mod from the game directory after user confirmation.
@@ -162,7 +670,7 @@ pub fn prepare_mod(app: tauri::AppHandle) -> Result<GameStatus, String> {
 /// Shows a warning dialogue explaining that the game will only be launchable via the Scopely Launcher afterwards.
 /// Returns the refreshed game status regardless of whether the user confirmed or cancelled.
 #[tauri::command]
-pub fn remove_mod(window: tauri::WebviewWindow) -> Result<GameStatus, String> {
+pub fn remove_mod(window: tauri::WebviewWindow) -> Result<GameStatus, CommandError> {
     // macOS: mod is injected via DYLD at launch, nothing to remove from disk
     #[cfg(not(target_os = "windows"))]
     #[allow(clippy::needless_return)]
@@ -173,10 +681,13 @@ pub fn remove_mod(window: tauri::WebviewWindow) -> Result<GameStatus, String> {
 
     #[cfg(target_os = "windows")]
     {
-        let info = game::detect().ok_or("STFC not found")?;
+        let info = game::detect().ok_or(game::GameError::NotInstalled)?;
 
         if game::is_running(&info.executable) {
-            return Err("Cannot remove mod while the game is running".to_string());
+            return Err(CommandError::new(
+                CommandErrorKind::GameRunning,
+                "Cannot remove mod while the game is running",
+            ));
         }
 
         let confirmed = window.dialog()
@@ -194,10 +705,22 @@ pub fn remove_mod(window: tauri::WebviewWindow) -> Result<GameStatus, String> {
 
         log_info!("User confirmed mod removal");
         game::remove_mod(&info.install_dir)?;
+        game::invalidate_detection();
         Ok(get_game_status(window.app_handle().clone()))
     }
 }
 
+/// Choose which of several detected installations to work with, or reset to the default with `None`.
+///
+/// The choice is honoured by all commands until Project Daystrom quits.
+/// Returns the refreshed game status for the selected installation.
+#[tauri::command]
+pub fn select_install(app: tauri::AppHandle, install_dir: Option<String>) -> Result<GameStatus, CommandError> {
+    game::select_install(install_dir.map(PathBuf::from))
+        .map_err(|message| CommandError::new(CommandErrorKind::NotFound, message))?;
+    Ok(get_game_status(app))
+}
+
 /// Result of checking the Scopely update API for a game update.
 #[derive(Clone, Serialize, TS)]
 #[ts(export)]
@@ -211,8 +734,8 @@ pub struct UpdateCheck {
 }
 
 /// Check whether a game update is available by comparing the local `.version` file against the Scopely update API.
-pub fn check_for_update() -> Result<UpdateCheck, String> {
-    let info = game::detect().ok_or("STFC not found")?;
+pub fn check_for_update() -> Result<UpdateCheck, CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
     let installed = info.installed_version.ok_or("Could not read installed game version")?;
 
     match game::version::fetch_remote(installed) {
@@ -228,7 +751,7 @@ pub fn check_for_update() -> Result<UpdateCheck, String> {
         }),
         Err(e) => {
             log_warn!("Update check failed: {e}");
-            Err(format!("Update check failed: {e}"))
+            Err(format!("Update check failed: {e}").into())
         }
     }
 }
@@ -245,33 +768,443 @@ pub struct ProcessStatus {
 
 /// Open the Scopely launcher so the user can install an update.
 #[tauri::command]
-pub fn launch_updater(_app: tauri::AppHandle) -> Result<(), String> {
+pub fn launch_updater(_app: tauri::AppHandle) -> Result<(), CommandError> {
     game::launcher::open_updater()?;
     Ok(())
 }
 
+/// Event emitted for each line of game output when [`launch_game`] or [`relaunch_game`] is called with
+/// `stream_output`.
+///
+/// The payload is a `GameOutputLine` with the line; stdout and stderr are not told apart.
+pub const GAME_OUTPUT: &str = "game-output";
+
+/// Return a sink that emits each game output line as a [`GAME_OUTPUT`] event, if `stream_output` is set.
+fn game_output_sink(app: &tauri::AppHandle, stream_output: Option<bool>) -> Option<game::launcher::OutputSink> {
+    if !stream_output.unwrap_or(false) {
+        return None;
+    }
+    let app = app.clone();
+    Some(Arc::new(move |line: game::launcher::GameOutputLine| {
+        let _ = app.emit(GAME_OUTPUT, line);
+    }))
+}
+
 /// Launch the game with the mod library injected.
 ///
-/// On macOS, checks entitlements before launching. On Windows, auto-deploys the DLL if needed.
+/// Optional `args` are passed through to the game executable (e.g. server or debug flags).
+/// Optional `library_paths` are prepended to `DYLD_LIBRARY_PATH` on macOS, for mods with dylib dependencies in other
+/// folders.
+/// With `stream_output`, the game's stdout and stderr are emitted live as [`GAME_OUTPUT`] events.
+/// On macOS, checks entitlements before launching; with `auto_patch`, missing ones are patched first instead of
+/// failing (see [`game::launcher::auto_patch`]), signed with the optional `identity` as in [`prepare_mod`].
+/// On Windows, auto-deploys the DLL if needed.
 #[tauri::command]
-pub fn launch_game(app: tauri::AppHandle) -> Result<(), String> {
-    let info = game::detect().ok_or("STFC not found")?;
+#[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+pub fn launch_game(
+    app: tauri::AppHandle,
+    args: Option<Vec<String>>,
+    library_paths: Option<Vec<String>>,
+    stream_output: Option<bool>,
+    auto_patch: Option<bool>,
+    identity: Option<String>,
+) -> Result<(), CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
 
-    let mod_library = game::find_mod_library(&app)
-        .ok_or("Mod library not found — run build:mod first")?;
+    if game::is_running(&info.executable) {
+        return Err(game::GameError::GameRunning.into());
+    }
 
-    // macOS: entitlements must be patched before launching
     #[cfg(target_os = "macos")]
-    {
-        let status = game::entitlements::check(&info.executable);
-        if !status.all_granted() {
-            let names: Vec<_> = status.missing.iter()
-                .map(|k| k.strip_prefix("com.apple.security.").unwrap_or(k))
-                .collect();
-            return Err(format!("Missing entitlements: {} — patch them first", names.join(", ")));
-        }
+    if auto_patch.unwrap_or(false) {
+        game::launcher::auto_patch(&info, |executable| patch_entitlements(&app, executable, identity.as_deref()))?;
+        game::invalidate_detection();
+    }
+
+    let mod_library = game::find_mod_library(&app).ok_or(game::GameError::ModNotFound)?;
+
+    let library_paths: Vec<PathBuf> = library_paths.unwrap_or_default().into_iter().map(PathBuf::from).collect();
+    let sink = game_output_sink(&app, stream_output);
+    game::launcher::launch(&info, &mod_library, &args.unwrap_or_default(), &library_paths, sink)?;
+    game::invalidate_detection();
+    Ok(())
+}
+
+/// Stop the game if it is running and launch it again with the mod injected, e.g. after patching entitlements.
+///
+/// Takes the same optional `args`, `library_paths` and `stream_output` as [`launch_game`]. Returns an error if the
+/// running game cannot be stopped or does not exit within the timeout, or if the launch fails.
+#[tauri::command]
+pub fn relaunch_game(
+    app: tauri::AppHandle,
+    args: Option<Vec<String>>,
+    library_paths: Option<Vec<String>>,
+    stream_output: Option<bool>,
+) -> Result<(), CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+    let mod_library = game::find_mod_library(&app).ok_or(game::GameError::ModNotFound)?;
+
+    let library_paths: Vec<PathBuf> = library_paths.unwrap_or_default().into_iter().map(PathBuf::from).collect();
+    let sink = game_output_sink(&app, stream_output);
+    game::launcher::relaunch(&info, &mod_library, &args.unwrap_or_default(), &library_paths, sink)?;
+    game::invalidate_detection();
+    Ok(())
+}
+
+/// Check a user-supplied dylib at `path` before injecting it: that it exists, is a Mach-O dynamic library, matches
+/// the game's architecture, and is not quarantined.
+///
+/// Returns a report with one flag per check and messages for the failed ones, so the UI can show what is wrong.
+#[tauri::command]
+pub fn validate_dylib(path: String) -> game::launcher::DylibReport {
+    let info = game::detect();
+    let report = game::launcher::validate_dylib(Path::new(&path), info.as_ref().map(|info| info.executable.as_path()));
+    if report.is_valid() {
+        log_info!("Dylib {path} passed validation");
+    } else {
+        log_info!("Dylib {path} failed validation: {}", report.messages.join("; "));
     }
+    report
+}
 
-    game::launcher::launch(&info, &mod_library)?;
+/// Launch the game without the mod.
+///
+/// Optional `args` are passed through like in [`launch_game`]. Neither a bundled mod library nor patched
+/// entitlements are required.
+#[tauri::command]
+pub fn launch_vanilla(args: Option<Vec<String>>) -> Result<(), CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+    game::launcher::launch_vanilla(&info, &args.unwrap_or_default())?;
+    game::invalidate_detection();
     Ok(())
 }
+
+/// Stop the running game.
+///
+/// Returns an error if the game is not installed, not running, or could not be terminated.
+#[tauri::command]
+pub fn stop_game() -> Result<(), CommandError> {
+    log_debug!("Stop game requested");
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+
+    if !game::is_running(&info.executable) {
+        return Err(CommandError::new(CommandErrorKind::Precondition, "Game is not running"));
+    }
+
+    Ok(game::launcher::stop(&info)?)
+}
+
+/// Return the version of the mod library the app would inject, or `None` if there is no library or it carries no
+/// version file.
+#[tauri::command]
+pub fn get_mod_version(app: tauri::AppHandle) -> Option<String> {
+    game::find_mod_library(&app).as_deref().and_then(game::mod_version)
+}
+
+/// Return the application version, read from the bundled package info so it always matches the running binary.
+#[tauri::command]
+pub fn get_app_version(app: tauri::AppHandle) -> String {
+    app.package_info().version.to_string()
+}
+
+/// Return the last `lines` lines of the current log file, oldest first, for the in-app log viewer.
+#[tauri::command]
+pub fn get_recent_logs(lines: usize) -> Result<Vec<String>, String> {
+    logging::tail_log(lines)
+}
+
+/// Return up to `limit` lines of the current log file, newest first, optionally only those of the logger named
+/// `logger` and at `min_level` (e.g. `"warn"`) or more severe.
+#[tauri::command]
+pub fn query_logs(logger: Option<String>, min_level: Option<String>, limit: usize) -> Result<Vec<String>, String> {
+    let min_level = min_level
+        .map(|level| level.parse::<log::Level>().map_err(|_| format!("Unknown log level: {level}")))
+        .transpose()?;
+    logging::query_log(logging::LogQuery { logger: logger.as_deref(), min_level }, limit)
+}
+
+/// Report the size of the current log file and the number and total size of the archives.
+///
+/// Returns zeros if the log directory does not exist yet.
+#[tauri::command]
+pub fn log_stats() -> logging::LogStats {
+    logging::log_stats()
+}
+
+/// Archive the current log file so logging starts fresh, and delete all older archives if `delete_archives` is set.
+///
+/// Returns the number of files archived or deleted.
+#[tauri::command]
+pub fn clear_logs(delete_archives: bool) -> Result<usize, String> {
+    let affected = logging::clear_logs(delete_archives)?;
+    log_info!("Cleared logs: {affected} file(s) archived or deleted");
+    Ok(affected)
+}
+
+/// Archive the current log file now instead of waiting for the next rotation, for a fresh log before reproducing a
+/// bug.
+///
+/// Returns the archive's file name, or `None` if the log file was empty. Fails if the log does not start with a
+/// timestamp or an archive was already written this second.
+#[tauri::command]
+pub fn rotate_now() -> Result<Option<String>, String> {
+    let archive = logging::rotate_now()?;
+    match &archive {
+        Some(name) => log_info!("Rotated log into {name}"),
+        None => log_info!("Log rotation requested, but there was nothing to archive"),
+    }
+    Ok(archive)
+}
+
+/// Return the log lines buffered in memory since startup, oldest first. New lines follow as `log-line` events.
+#[tauri::command]
+pub fn get_log_buffer() -> Vec<String> {
+    logging::buffered_lines()
+}
+
+/// Write a diagnostics zip for support requests to `dest` (a file path or a directory) and return its final path.
+///
+/// The bundle contains the current log, the most recent archives, the game status, and on macOS the entitlements of
+/// the game executable, with the home directory replaced by `~`.
+#[tauri::command]
+pub fn export_diagnostics(app: tauri::AppHandle, dest: String) -> Result<String, String> {
+    let status = game_status_only(&app);
+    let game_status = serde_json::to_string_pretty(&status)
+        .map_err(|e| format!("Failed to serialise the game status: {e}"))?;
+    #[cfg(target_os = "macos")]
+    let entitlements = Some(get_entitlements_xml().map_err(|e| e.message));
+    #[cfg(not(target_os = "macos"))]
+    let entitlements = None;
+
+    let report = diagnostics::Report { game_status, entitlements };
+    let path = diagnostics::export(Path::new(&dest), &report)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Open the application's log directory in the system file manager.
+///
+/// Returns an error if the platform has no log directory or it has not been created yet.
+#[tauri::command]
+pub fn open_log_dir() -> Result<(), String> {
+    let dir = logging::log_dir().ok_or("No log directory on this platform")?;
+    if !dir.is_dir() {
+        return Err(format!("Log directory does not exist yet: {}", dir.display()));
+    }
+    log_debug!("Opening log directory {}", dir.display());
+    open_in_file_manager(&dir)
+}
+
+/// Show the STFC installation in the system file manager (Finder on macOS, Explorer on Windows).
+///
+/// On macOS the `.app` bundle is selected, on Windows the executable; elsewhere the install directory is opened.
+#[tauri::command]
+pub fn reveal_game() -> Result<(), CommandError> {
+    let info = game::detect().ok_or(game::GameError::NotInstalled)?;
+
+    #[cfg(target_os = "macos")]
+    let target = game::app_bundle(&info.executable).unwrap_or(&info.executable);
+    #[cfg(target_os = "windows")]
+    let target = info.executable.as_path();
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let target = info.install_dir.as_path();
+
+    log_info!("Revealing game at {}", target.display());
+    Ok(reveal_in_file_manager(target)?)
+}
+
+/// Select a path in the system file manager (`open -R` on macOS, `explorer /select,` on Windows).
+///
+/// On other platforms there is no portable way to select a file, so the path itself is opened.
+fn reveal_in_file_manager(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let (program, args) = ("open", vec!["-R".into(), path.as_os_str().to_owned()]);
+    #[cfg(target_os = "windows")]
+    let (program, args) = ("explorer", vec![{
+        let mut arg = std::ffi::OsString::from("/select,");
+        arg.push(path);
+        arg
+    }]);
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let (program, args) = ("xdg-open", vec![path.as_os_str().to_owned()]);
+
+    game::silent_command(program)
+        .args(args)
+        .spawn()
+        .map_err(|e| {
+            log_error!("Failed to reveal {} with {program}: {e}", path.display());
+            "Failed to open file manager (see log for details)".to_string()
+        })?;
+    Ok(())
+}
+
+/// Open a path in the system file manager (`open` on macOS, `explorer` on Windows, `xdg-open` elsewhere).
+///
+/// The process is spawned but not awaited, since `explorer` reports a non-zero exit code even on success.
+fn open_in_file_manager(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let program = "xdg-open";
+
+    game::silent_command(program)
+        .arg(path)
+        .spawn()
+        .map_err(|e| {
+            log_error!("Failed to open {} with {program}: {e}", path.display());
+            "Failed to open file manager (see log for details)".to_string()
+        })?;
+    Ok(())
+}
+
+// ---- Tests ----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUIRED: [&str; 2] =
+        ["com.apple.security.cs.disable-library-validation", "com.apple.security.get-task-allow"];
+
+    /// A [`StatusProbe`] answering from fixed values instead of the system.
+    struct FakeProbe {
+        installs: Vec<game::GameInfo>,
+        entitlements: Result<Vec<EntitlementState>, String>,
+        running: bool,
+        mod_library: Option<PathBuf>,
+    }
+
+    impl FakeProbe {
+        fn installed(entitlements: Vec<EntitlementState>) -> Self {
+            FakeProbe {
+                installs: vec![install("/Games/STFC")],
+                entitlements: Ok(entitlements),
+                running: false,
+                mod_library: Some(PathBuf::from("/App/Resources/mod.dylib")),
+            }
+        }
+    }
+
+    fn install(dir: &str) -> game::GameInfo {
+        game::GameInfo {
+            install_dir: PathBuf::from(dir),
+            executable: Path::new(dir).join("prime"),
+            installed_version: Some(42),
+            bundle_version: Some("1.0.42".to_string()),
+        }
+    }
+
+    impl StatusProbe for FakeProbe {
+        fn mod_library(&self) -> Option<PathBuf> {
+            self.mod_library.clone()
+        }
+
+        fn mod_version(&self, _library: &Path) -> Option<String> {
+            Some("0.3.0".to_string())
+        }
+
+        fn launcher_running(&self) -> bool {
+            false
+        }
+
+        fn detect_all(&self) -> Vec<game::GameInfo> {
+            self.installs.clone()
+        }
+
+        fn detect(&self) -> Option<game::GameInfo> {
+            self.installs.first().cloned()
+        }
+
+        fn check_entitlements(&self, _executable: &Path) -> Result<game::entitlements::EntitlementStatus, String> {
+            let states: BTreeMap<_, _> = REQUIRED.into_iter().zip(self.entitlements.clone()?).collect();
+            let missing = states.iter().filter(|(_, state)| **state != EntitlementState::Granted).map(|(key, _)| *key);
+            Ok(game::entitlements::EntitlementStatus { missing: missing.collect(), states })
+        }
+
+        fn is_running(&self, _executable: &Path) -> bool {
+            self.running
+        }
+
+        fn game_arch(&self, _executable: &Path) -> Option<String> {
+            Some("native".to_string())
+        }
+
+        #[cfg(target_os = "windows")]
+        fn mod_deployment(&self, _install_dir: &Path, _mod_library: &Path) -> game::ModDeploymentState {
+            game::ModDeploymentState::NotDeployed
+        }
+    }
+
+    #[test]
+    fn status_not_installed() {
+        let probe = FakeProbe { installs: Vec::new(), ..FakeProbe::installed(Vec::new()) };
+        let status = game_status(&probe);
+
+        assert!(!status.installed);
+        assert_eq!(status.install_dir, None);
+        assert!(status.mod_available, "the bundled mod is found even without the game");
+        assert_eq!(status.mod_version.as_deref(), Some("0.3.0"));
+        assert!(!status.mod_installable);
+        assert!(!status.mod_deployed);
+        assert!(status.entitlements.is_empty());
+        assert!(!status.can_patch_now);
+        assert!(!status.needs_restart_to_patch);
+    }
+
+    #[test]
+    fn status_installed_with_entitlements() {
+        let mut probe = FakeProbe::installed(vec![EntitlementState::Granted; 2]);
+        probe.installs.push(install("/Steam/STFC"));
+        let status = game_status(&probe);
+
+        assert!(status.installed);
+        assert_eq!(status.game_version, Some(42));
+        assert_eq!(status.bundle_version.as_deref(), Some("1.0.42"));
+        assert_eq!(status.install_dir.as_deref(), Some(Path::new("/Games/STFC").display().to_string().as_str()));
+        assert_eq!(status.installations.len(), 2);
+        assert!(status.mod_installable);
+        assert_eq!(status.entitlements.len(), 2);
+        assert!(status.entitlements.values().all(|state| *state == EntitlementState::Granted));
+        assert_eq!(status.entitlements_error, None);
+        assert!(!status.game_running);
+        assert_eq!(status.game_arch, None, "the architecture is only looked up for a running game");
+        assert_eq!(status.can_patch_now, cfg!(any(target_os = "macos", target_os = "windows")));
+        assert!(!status.needs_restart_to_patch);
+        #[cfg(target_os = "macos")]
+        assert!(status.mod_deployed);
+    }
+
+    #[test]
+    fn status_missing_entitlements() {
+        let probe = FakeProbe::installed(vec![EntitlementState::Absent, EntitlementState::Granted]);
+        let status = game_status(&probe);
+
+        assert_eq!(status.entitlements[REQUIRED[0]], EntitlementState::Absent);
+        assert_eq!(status.entitlements[REQUIRED[1]], EntitlementState::Granted);
+        assert_eq!(status.can_patch_now, cfg!(any(target_os = "macos", target_os = "windows")));
+        assert!(!status.mod_deployed);
+        assert!(!status.needs_restart_to_patch);
+
+        let status = game_status(&FakeProbe { running: true, ..probe });
+        assert!(status.game_running);
+        assert_eq!(status.game_arch.as_deref(), Some("native"));
+        assert!(!status.can_patch_now);
+        #[cfg(any(target_os = "macos", target_os = "windows"))]
+        assert!(status.needs_restart_to_patch);
+    }
+
+    #[test]
+    fn status_entitlements_not_checkable() {
+        let probe = FakeProbe {
+            entitlements: Err("codesign not found".to_string()),
+            ..FakeProbe::installed(Vec::new())
+        };
+        let status = game_status(&probe);
+
+        assert!(status.installed);
+        assert!(status.entitlements.is_empty());
+        assert_eq!(status.entitlements_error.as_deref(), Some("codesign not found"));
+        assert!(!status.mod_deployed);
+    }
+}
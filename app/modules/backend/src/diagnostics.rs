@@ -0,0 +1,189 @@
+use std::fs;
+use std::io::{self, Cursor, Write};
+use std::path::{Path, PathBuf};
+
+use zip::write::SimpleFileOptions;
+
+use crate::logging;
+use crate::use_log;
+
+use_log!("Diagnostics");
+
+/// Number of archived log files included in a diagnostics bundle, newest first.
+const ARCHIVES_IN_BUNDLE: usize = 5;
+
+/// File name used when the export destination is a directory.
+const DEFAULT_BUNDLE_PREFIX: &str = "project-daystrom-diagnostics";
+
+/// Contents of a diagnostics bundle gathered by the caller, in addition to the log files.
+pub struct Report {
+    /// `get_game_status` output, serialised as JSON.
+    pub game_status: String,
+    /// Raw entitlements plist of the game executable, or the reason it could not be read; `None` if not applicable.
+    pub entitlements: Option<Result<String, String>>,
+}
+
+/// Write a diagnostics zip with the current log, the most recent archives, and `report` to `dest`.
+///
+/// If `dest` is an existing directory, the bundle gets a timestamped name inside it. The home directory prefix is
+/// replaced with `~` in all text files, so the bundle can be shared without exposing the user name. Returns the path
+/// of the written file.
+pub fn export(dest: &Path, report: &Report) -> Result<PathBuf, String> {
+    let path = bundle_path(dest);
+    let home = dirs::home_dir();
+    let redact = |text: &str| match &home {
+        Some(home) => redact_home(text, home),
+        None => text.to_string(),
+    };
+
+    let mut files = vec![("game-status.json".to_string(), redact(&report.game_status))];
+    match &report.entitlements {
+        Some(Ok(xml)) => files.push(("entitlements.xml".to_string(), redact(xml))),
+        Some(Err(e)) => files.push(("entitlements-error.txt".to_string(), redact(e))),
+        None => {}
+    }
+
+    for file in logging::recent_log_files(ARCHIVES_IN_BUNDLE) {
+        let Some(name) = file.file_name().map(|n| n.to_string_lossy().into_owned()) else { continue };
+        match read_log_text(&file) {
+            Ok(text) => {
+                let name = name.strip_suffix(".gz").or_else(|| name.strip_suffix(".zst")).unwrap_or(&name);
+                files.push((format!("logs/{name}"), redact(&text)));
+            }
+            Err(e) => log_warn!("Skipping {} in diagnostics bundle: {e}", file.display()),
+        }
+    }
+
+    let bytes = build_zip(&files).map_err(|e| format!("Failed to build the diagnostics bundle: {e}"))?;
+    fs::write(&path, bytes).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    log_info!("Diagnostics exported to {}", path.display());
+    Ok(path)
+}
+
+/// Resolve the bundle path: `dest` itself, or a timestamped file name inside `dest` if it is a directory.
+fn bundle_path(dest: &Path) -> PathBuf {
+    if !dest.is_dir() {
+        return dest.to_path_buf();
+    }
+    let now = time::OffsetDateTime::now_local().unwrap_or_else(|_| time::OffsetDateTime::now_utc());
+    let stamp = now
+        .format(time::macros::format_description!("[year]-[month]-[day]_[hour]-[minute]-[second]"))
+        .unwrap_or_default();
+    dest.join(format!("{DEFAULT_BUNDLE_PREFIX}_{stamp}.zip"))
+}
+
+/// Read a log file as text, decompressing gzip and zstd archives.
+fn read_log_text(path: &Path) -> io::Result<String> {
+    let bytes = logging::read_archive(path)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Replace every occurrence of the home directory in `text` with `~`.
+///
+/// Only whole path components match: the home path must be followed by a path separator or end the token, so a
+/// sibling such as `/Users/janet` next to home `/Users/jane` is left alone.
+fn redact_home(text: &str, home: &Path) -> String {
+    let home = home.to_string_lossy();
+    let home = home.trim_end_matches(['/', '\\']);
+    if home.is_empty() {
+        return text.to_string();
+    }
+
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(home) {
+        redacted.push_str(&rest[..pos]);
+        rest = &rest[pos + home.len()..];
+        redacted.push_str(if continues_name(rest) { home } else { "~" });
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+/// Whether `rest` continues the file name just before it, e.g. `t` in `/Users/janet` or `.bak` in `jane.bak`. A dot
+/// that ends a sentence does not.
+fn continues_name(rest: &str) -> bool {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_');
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some('.') => chars.next().is_some_and(is_name_char),
+        Some(c) => is_name_char(c),
+        None => false,
+    }
+}
+
+/// Pack `files` (name and text content) into a zip archive with deflate-compressed entries, dated now.
+fn build_zip(files: &[(String, String)]) -> zip::result::ZipResult<Vec<u8>> {
+    let now = time::OffsetDateTime::now_local().unwrap_or_else(|_| time::OffsetDateTime::now_utc());
+    let modified = zip::DateTime::try_from(time::PrimitiveDateTime::new(now.date(), now.time())).unwrap_or_default();
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .last_modified_time(modified);
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, text) in files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(text.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+// ---- Tests ----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn build_zip_round_trip() {
+        let files = [
+            ("game-status.json".to_string(), "{\"installed\":true}".to_string()),
+            ("logs/project-daystrom.log".to_string(), "line with Über\n".repeat(50)),
+        ];
+        let bytes = build_zip(&files).unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), 2);
+        for (name, text) in &files {
+            let mut entry = archive.by_name(name).unwrap();
+            assert_eq!(entry.compression(), zip::CompressionMethod::Deflated);
+            let mut content = String::new();
+            entry.read_to_string(&mut content).unwrap();
+            assert_eq!(&content, text);
+        }
+    }
+
+    #[test]
+    fn redact_home_replaces_prefix() {
+        let home = Path::new("/Users/jane");
+        assert_eq!(
+            redact_home("GAME_PATH=/Users/jane/Games/STFC and /Users/jane/", home),
+            "GAME_PATH=~/Games/STFC and ~/"
+        );
+        assert_eq!(redact_home("/opt/stfc", home), "/opt/stfc");
+        assert_eq!(redact_home("/opt/stfc", Path::new("/")), "/opt/stfc");
+        assert_eq!(redact_home("home is /Users/jane", home), "home is ~");
+        assert_eq!(redact_home("\"/Users/jane\": not found", home), "\"~\": not found");
+        assert_eq!(redact_home("/Users/janet/Games", home), "/Users/janet/Games");
+        assert_eq!(redact_home("/Users/jane.bak and /Users/jane.", home), "/Users/jane.bak and ~.");
+        assert_eq!(redact_home(r"C:\Users\jane\AppData", Path::new(r"C:\Users\jane")), r"~\AppData");
+    }
+
+    #[test]
+    fn bundle_path_in_directory() {
+        let dir = std::env::temp_dir().join("daystrom_test_diagnostics_dest");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = bundle_path(&dir);
+        assert_eq!(path.parent(), Some(dir.as_path()));
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with(DEFAULT_BUNDLE_PREFIX) && name.ends_with(".zip"), "unexpected name {name}");
+
+        let file = dir.join("support.zip");
+        assert_eq!(bundle_path(&file), file);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,7 +1,15 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
-use std::process::Command;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::GameError;
 use crate::use_log;
 
 use_log!("Entitlements");
@@ -14,10 +22,28 @@ const REQUIRED: [&str; 4] = [
     "com.apple.security.get-task-allow",
 ];
 
+/// File name of the entitlements backup, stored in the install directory next to the `.app` bundle.
+const BACKUP_FILE_NAME: &str = ".daystrom-entitlements-backup.plist";
+
+/// State of a single entitlement in the game executable's code signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum EntitlementState {
+    /// The key is present with `<true/>`.
+    Granted,
+    /// The key is present with any other value (usually `<false/>`).
+    Denied,
+    /// The key is not present in the signature.
+    Absent,
+}
+
 /// Result of checking the game executable's code-signing entitlements.
 pub struct EntitlementStatus {
     /// Entitlement keys that are absent or not `true`.
     pub missing: Vec<&'static str>,
+    /// State of each of the four required entitlements, keyed by entitlement name.
+    pub states: BTreeMap<&'static str, EntitlementState>,
 }
 
 impl EntitlementStatus {
@@ -25,124 +51,226 @@ impl EntitlementStatus {
     pub fn all_granted(&self) -> bool {
         self.missing.is_empty()
     }
+
+    /// Missing entitlement keys without the `com.apple.security.` prefix, for messages shown to the user.
+    pub fn missing_short(&self) -> Vec<&'static str> {
+        self.missing.iter().map(|key| short_name(key)).collect()
+    }
+
+    /// Granted entitlement keys without the `com.apple.security.` prefix, for messages shown to the user.
+    pub fn granted_short(&self) -> Vec<&'static str> {
+        self.states
+            .iter()
+            .filter(|(_, state)| **state == EntitlementState::Granted)
+            .map(|(key, _)| short_name(key))
+            .collect()
+    }
+}
+
+/// Strip the `com.apple.security.` prefix from an entitlement key; other keys are returned unchanged.
+fn short_name(key: &'static str) -> &'static str {
+    key.strip_prefix("com.apple.security.").unwrap_or(key)
+}
+
+impl EntitlementStatus {
+    /// Build the status from the state of each required entitlement.
+    fn from_states(states: BTreeMap<&'static str, EntitlementState>) -> Self {
+        let missing = REQUIRED
+            .iter()
+            .copied()
+            .filter(|key| states.get(key) != Some(&EntitlementState::Granted))
+            .collect();
+        EntitlementStatus { missing, states }
+    }
 }
 
-/// Check whether a plist XML fragment contains `<key>{key}</key>` followed by `<true/>`.
-fn has_entitlement(xml: &str, key: &str) -> bool {
+/// Determine the state of `<key>{key}</key>` in a plist XML fragment.
+///
+/// Returns [`EntitlementState::Granted`] when the key is followed by `<true/>`, [`EntitlementState::Denied`] when it
+/// is followed by anything else, and [`EntitlementState::Absent`] when the key does not occur.
+fn entitlement_state(xml: &str, key: &str) -> EntitlementState {
     let needle = format!("<key>{key}</key>");
     let Some(pos) = xml.find(&needle) else {
-        return false;
+        return EntitlementState::Absent;
     };
-    xml[pos + needle.len()..].trim_start().starts_with("<true/>")
+    if xml[pos + needle.len()..].trim_start().starts_with("<true/>") {
+        EntitlementState::Granted
+    } else {
+        EntitlementState::Denied
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const FULL_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
-<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
-<plist version="1.0">
-<dict>
-    <key>com.apple.security.cs.allow-dyld-environment-variables</key>
-    <true/>
-    <key>com.apple.security.cs.allow-unsigned-executable-memory</key>
-    <true/>
-    <key>com.apple.security.cs.disable-library-validation</key>
-    <true/>
-    <key>com.apple.security.get-task-allow</key>
-    <true/>
-</dict>
-</plist>"#;
-
-    #[test]
-    fn has_entitlement_present_and_true() {
-        assert!(has_entitlement(
-            FULL_PLIST,
-            "com.apple.security.cs.allow-dyld-environment-variables",
-        ));
-    }
+/// Query the code signature of `executable` and check which of the four
+/// required mod-injection entitlements are present.
+///
+/// Returns an error if the signature could not be inspected at all (e.g. `codesign` is not installed), so callers
+/// can tell "couldn't check" apart from "definitely missing". An unsigned executable is reported as all absent.
+pub fn check(executable: &Path) -> Result<EntitlementStatus, String> {
+    log_debug!("Checking entitlements on {}", executable.display());
 
-    #[test]
-    fn has_entitlement_present_but_false() {
-        let xml = r#"<dict>
-    <key>com.apple.security.get-task-allow</key>
-    <false/>
-</dict>"#;
-        assert!(!has_entitlement(xml, "com.apple.security.get-task-allow"));
-    }
+    let xml = read_entitlements_xml(executable)?;
+    Ok(EntitlementStatus::from_states(
+        REQUIRED.iter().map(|key| (*key, entitlement_state(&xml, key))).collect(),
+    ))
+}
 
-    #[test]
-    fn has_entitlement_missing_key() {
-        assert!(!has_entitlement(FULL_PLIST, "com.apple.security.app-sandbox"));
-    }
+/// Environment variable that, set to `1`, runs `codesign` verbosely in [`check`] and [`patch`] and logs its full
+/// output at info level, so signing problems can be diagnosed from a shared log file.
+pub const CODESIGN_VERBOSE_ENV: &str = "DAYSTROM_CODESIGN_VERBOSE";
 
-    #[test]
-    fn has_entitlement_empty_xml() {
-        assert!(!has_entitlement("", "com.apple.security.get-task-allow"));
-    }
+/// Whether [`CODESIGN_VERBOSE_ENV`] is enabled.
+fn codesign_verbose() -> bool {
+    std::env::var(CODESIGN_VERBOSE_ENV).as_deref() == Ok("1")
+}
 
-    #[test]
-    fn has_entitlement_key_without_value() {
-        let xml = "<dict><key>com.apple.security.get-task-allow</key></dict>";
-        assert!(!has_entitlement(xml, "com.apple.security.get-task-allow"));
+/// Create a `codesign` command, starting with `verbosity` (`-v` or `-vv`) if [`codesign_verbose`] is enabled.
+fn codesign_command(verbosity: &str) -> Command {
+    let mut cmd = Command::new("codesign");
+    if codesign_verbose() {
+        cmd.arg(verbosity);
     }
+    cmd
+}
 
-    #[test]
-    fn has_entitlement_tolerates_whitespace_variants() {
-        // Value on same line as key
-        let xml = "<key>com.apple.security.get-task-allow</key><true/>";
-        assert!(has_entitlement(xml, "com.apple.security.get-task-allow"));
-
-        // Extra whitespace / newlines between key and value
-        let xml = "<key>com.apple.security.get-task-allow</key>\n\t\t<true/>";
-        assert!(has_entitlement(xml, "com.apple.security.get-task-allow"));
+/// Log the exit status, stdout and stderr of a `codesign` run at info level if [`codesign_verbose`] is enabled.
+fn log_codesign_output(action: &str, output: &Output) {
+    if !codesign_verbose() {
+        return;
     }
+    log_info!(
+        "codesign {action} exited with {}\nstdout:\n{}\nstderr:\n{}",
+        output.status,
+        String::from_utf8_lossy(&output.stdout).trim_end(),
+        String::from_utf8_lossy(&output.stderr).trim_end()
+    );
 }
 
-/// Query the code signature of `executable` and check which of the four
-/// required mod-injection entitlements are present.
-pub fn check(executable: &Path) -> EntitlementStatus {
-    log_debug!("Checking entitlements on {}", executable.display());
+/// Marker in `codesign` stderr for an executable without any code signature.
+const NOT_SIGNED_MARKER: &str = "code object is not signed at all";
 
-    let output = Command::new("codesign")
+/// Dump the entitlements of the current code signature as an XML plist via `codesign -d`.
+///
+/// An executable without entitlements or without any signature yields an empty string. Returns an error (with
+/// warn logging) carrying `codesign`'s stderr if it cannot be run or fails for any other reason.
+pub fn read_entitlements_xml(executable: &Path) -> Result<String, String> {
+    let output = codesign_command("-v")
         .args(["-d", "--entitlements", ":-", "--xml"])
         .arg(executable)
         .output();
+    if let Ok(out) = &output {
+        log_codesign_output("display", out);
+    }
 
-    let xml = match output {
-        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).into_owned(),
+    match output {
+        Ok(out) if out.status.success() => Ok(String::from_utf8_lossy(&out.stdout).into_owned()),
         Ok(out) => {
             let stderr = String::from_utf8_lossy(&out.stderr);
-            log_debug!("codesign failed: {stderr}");
-            return EntitlementStatus { missing: REQUIRED.to_vec() };
+            if stderr.contains(NOT_SIGNED_MARKER) {
+                log_debug!("Executable is not signed: {stderr}");
+                return Ok(String::new());
+            }
+            log_warn!("codesign failed ({}): {stderr}", out.status);
+            Err(format!("codesign failed: {}", stderr.trim()))
         }
         Err(e) => {
-            log_debug!("Could not run codesign: {e}");
-            return EntitlementStatus { missing: REQUIRED.to_vec() };
+            log_warn!("Could not run codesign: {e}");
+            Err(format!("Could not run codesign: {e}"))
         }
-    };
-
-    let missing: Vec<_> = REQUIRED.iter().copied().filter(|key| !has_entitlement(&xml, key)).collect();
-
-    EntitlementStatus { missing }
+    }
 }
 
-/// XML plist containing the four required entitlements for mod injection.
-const ENTITLEMENTS_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// Header of a plist XML document, up to and including the opening `<dict>`.
+const PLIST_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
 <plist version="1.0">
 <dict>
-    <key>com.apple.security.cs.allow-dyld-environment-variables</key>
-    <true/>
-    <key>com.apple.security.cs.allow-unsigned-executable-memory</key>
-    <true/>
-    <key>com.apple.security.cs.disable-library-validation</key>
-    <true/>
-    <key>com.apple.security.get-task-allow</key>
-    <true/>
-</dict>
-</plist>"#;
+"#;
+
+/// Build an XML plist granting the four [`REQUIRED`] entitlements plus any `extra` keys.
+///
+/// Extra keys that duplicate a required one (or each other) are written only once. Keys are XML-escaped, so the
+/// result is always well-formed.
+fn entitlements_plist(extra: &[&str]) -> String {
+    let mut keys: Vec<&str> = REQUIRED.to_vec();
+    for key in extra {
+        if !keys.contains(key) {
+            keys.push(key);
+        }
+    }
+
+    let mut xml = PLIST_HEADER.to_string();
+    for key in keys {
+        let key = key.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        xml.push_str(&format!("    <key>{key}</key>\n    <true/>\n"));
+    }
+    xml.push_str("</dict>\n</plist>");
+    xml
+}
+
+/// Check that a user-supplied entitlements plist is a well-formed XML plist that grants all [`REQUIRED`] keys.
+///
+/// Hand-rolled like [`entitlement_state`]: the document must have a `<plist>` root wrapping a `<dict>`, and no
+/// unbalanced tags. Missing or denied required keys are listed in the error without the `com.apple.security.` prefix.
+fn validate_entitlements_plist(xml: &str) -> Result<(), String> {
+    let body = xml.trim();
+    let body = match body.strip_prefix("<?xml") {
+        Some(rest) => rest.split_once("?>").map(|(_, rest)| rest.trim_start()).ok_or("Unterminated XML declaration")?,
+        None => body,
+    };
+    let body = match body.strip_prefix("<!DOCTYPE") {
+        Some(rest) => rest.split_once('>').map(|(_, rest)| rest.trim_start()).ok_or("Unterminated DOCTYPE")?,
+        None => body,
+    };
+    if !body.starts_with("<plist") || !body.ends_with("</plist>") {
+        return Err("Not a plist document".to_string());
+    }
+
+    let mut open: Vec<&str> = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find('<') {
+        if rest[start..].starts_with("<!--") {
+            let end = rest[start..].find("-->").ok_or("Unterminated comment")? + start;
+            rest = &rest[end + 3..];
+            continue;
+        }
+        let end = rest[start..].find('>').ok_or("Unterminated tag")? + start;
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+        if tag.ends_with('/') {
+            continue;
+        }
+        let name = tag.trim_start_matches('/').split_whitespace().next().unwrap_or_default();
+        if tag.starts_with('/') {
+            if open.pop() != Some(name) {
+                return Err(format!("Unexpected closing tag </{name}>"));
+            }
+        } else {
+            open.push(name);
+        }
+    }
+    if !open.is_empty() {
+        return Err(format!("Unclosed tag <{}>", open.join(">, <")));
+    }
+    if !body.contains("<dict>") {
+        return Err("Plist has no dictionary".to_string());
+    }
+
+    let missing: Vec<&str> = REQUIRED.iter()
+        .filter(|key| entitlement_state(body, key) != EntitlementState::Granted)
+        .map(|key| short_name(key))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Required entitlements not granted: {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+/// Read a user-supplied entitlements plist and check it with [`validate_entitlements_plist`].
+fn read_entitlements_file(path: &Path) -> Result<String, String> {
+    let xml = fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    validate_entitlements_plist(&xml).map_err(|e| format!("Invalid entitlements file {}: {e}", path.display()))?;
+    Ok(xml)
+}
 
 /// Recursively remove leftover `.temp` files from the app bundle's `Contents` directory.
 ///
@@ -187,54 +315,758 @@ fn remove_temp_files_recursive(dir: &Path) {
     }
 }
 
-/// Re-sign the game executable with the four required entitlements for mod injection.
+/// Return the path of the entitlements backup for the given game executable.
 ///
-/// Cleans up leftover temp files from the Scopely updater first, then writes a temporary
-/// plist file, runs `codesign --force --sign -` with it, and verifies the result.
-pub fn patch(executable: &Path) -> Result<(), String> {
-    log_info!("Patching entitlements on {}", executable.display());
+/// The executable lives at `<install_dir>/<name>.app/Contents/MacOS/<name>`; the backup goes into `<install_dir>`.
+fn backup_path(executable: &Path) -> Option<PathBuf> {
+    Some(executable.ancestors().nth(4)?.join(BACKUP_FILE_NAME))
+}
+
+/// Whether an entitlements backup from a previous [`backup`] exists for the given game executable.
+pub fn has_backup(executable: &Path) -> bool {
+    backup_path(executable).is_some_and(|path| path.is_file())
+}
+
+/// Save the executable's current entitlements so they can be restored after patching.
+///
+/// Writes the `codesign -d --entitlements` XML to [`BACKUP_FILE_NAME`] next to the `.app` bundle. An existing backup
+/// is never overwritten, so repeated patching keeps the entitlements from before the first patch.
+pub fn backup(executable: &Path) -> Result<(), String> {
+    let path = backup_path(executable).ok_or("Could not determine the backup location")?;
+    if path.exists() {
+        log_debug!("Entitlements backup already exists: {}", path.display());
+        return Ok(());
+    }
+
+    let xml = read_entitlements_xml(executable)?;
+    fs::write(&path, xml).map_err(|e| {
+        log_error!("Failed to write entitlements backup {}: {e}", path.display());
+        format!("Failed to write entitlements backup: {e}")
+    })?;
+    log_info!("Backed up entitlements to {}", path.display());
+    Ok(())
+}
+
+/// Re-sign the game executable with the entitlements saved by [`backup`] and remove the backup.
+///
+/// The original developer signature cannot be recreated, so the executable is signed ad hoc with the original
+/// entitlements. Returns an error if no backup exists or `codesign` fails.
+pub fn restore(executable: &Path) -> Result<(), String> {
+    let path = backup_path(executable).ok_or("Could not determine the backup location")?;
+    let xml = fs::read_to_string(&path).map_err(|e| {
+        log_warn!("Could not read entitlements backup {}: {e}", path.display());
+        "No entitlements backup found".to_string()
+    })?;
+
+    log_info!("Restoring entitlements on {} from {}", executable.display(), path.display());
 
-    // Clean up Scopely updater leftovers that would make codesign fail
     clean_bundle_temp_files(executable);
+    let entitlements = (!xml.trim().is_empty()).then_some(xml.as_str());
+    preserving_mtime(executable, || sign(executable, entitlements, None)).map_err(|e| {
+        log_error!("codesign failed: {e}");
+        "Entitlement restore failed (see log for details)".to_string()
+    })?;
+
+    if let Err(e) = fs::remove_file(&path) {
+        log_warn!("Could not remove entitlements backup {}: {e}", path.display());
+    }
+    log_info!("Entitlements restored");
+    Ok(())
+}
+
+/// Maximum number of `codesign` attempts in [`sign`] when it fails with a transient error.
+const CODESIGN_ATTEMPTS: u32 = 3;
+
+/// Delay before the first `codesign` retry; doubled for each further attempt.
+const CODESIGN_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// `codesign` stderr fragments that indicate a temporary condition (e.g. the launcher still holding the file)
+/// rather than a genuine signing error.
+const TRANSIENT_CODESIGN_ERRORS: [&str; 2] = ["resource busy", "resource temporarily unavailable"];
+
+/// Whether a `codesign` failure looks transient and is worth retrying.
+fn is_transient_codesign_error(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    TRANSIENT_CODESIGN_ERRORS.iter().any(|fragment| stderr.contains(fragment))
+}
 
+/// Sign the executable with the hardened runtime, optionally embedding the given entitlements plist.
+///
+/// Signs with `identity` (a certificate name or SHA-1 hash, as accepted by `codesign --sign`) if given, ad hoc
+/// otherwise.
+///
+/// The plist is written to a temporary file for `codesign --entitlements` and removed afterwards. Transient failures
+/// (see [`is_transient_codesign_error`]) are retried up to [`CODESIGN_ATTEMPTS`] times with a growing delay.
+/// Returns `codesign`'s stderr on failure.
+fn sign(executable: &Path, entitlements: Option<&str>, identity: Option<&str>) -> Result<(), String> {
     let plist_path = std::env::temp_dir().join("daystrom-entitlements.plist");
 
-    fs::write(&plist_path, ENTITLEMENTS_PLIST)
-        .map_err(|e| format!("Failed to write entitlements plist: {e}"))?;
-
-    let output = Command::new("codesign")
-        .args([
-            "--force",
-            "--sign",
-            "-",
-            "--options",
-            "runtime",
-            "--entitlements",
-        ])
-        .arg(&plist_path)
+    let mut cmd = codesign_command("-v");
+    cmd.args(["--force", "--sign", identity.unwrap_or("-"), "--options", "runtime"]);
+    if let Some(xml) = entitlements {
+        fs::write(&plist_path, xml)
+            .map_err(|e| format!("Failed to write entitlements plist: {e}"))?;
+        cmd.arg("--entitlements").arg(&plist_path);
+    }
+    cmd.arg(executable);
+
+    let mut result = Ok(());
+    let mut delay = CODESIGN_RETRY_DELAY;
+    for attempt in 1..=CODESIGN_ATTEMPTS {
+        result = match cmd.output() {
+            Ok(output) => {
+                log_codesign_output("sign", &output);
+                if output.status.success() {
+                    Ok(())
+                } else {
+                    Err(String::from_utf8_lossy(&output.stderr).into_owned())
+                }
+            }
+            Err(e) => Err(format!("Failed to run codesign: {e}")),
+        };
+        match &result {
+            Err(stderr) if attempt < CODESIGN_ATTEMPTS && is_transient_codesign_error(stderr) => {
+                log_warn!(
+                    "codesign attempt {attempt}/{CODESIGN_ATTEMPTS} failed transiently, retrying in {}ms: {}",
+                    delay.as_millis(),
+                    stderr.trim()
+                );
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            _ => break,
+        }
+    }
+
+    // Clean up temp file regardless of outcome
+    if entitlements.is_some() {
+        let _ = fs::remove_file(&plist_path);
+    }
+
+    result
+}
+
+/// Check that the signature of `executable` is structurally valid, via `codesign --verify --deep --strict`.
+///
+/// Returns the verifier's stderr on failure, or a message if `codesign` cannot be run.
+fn verify_signature(executable: &Path) -> Result<(), String> {
+    let output = codesign_command("-vv")
+        .args(["--verify", "--deep", "--strict"])
         .arg(executable)
         .output()
         .map_err(|e| format!("Failed to run codesign: {e}"))?;
+    log_codesign_output("verify", &output);
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
 
-    // Clean up temp file regardless of outcome
-    let _ = fs::remove_file(&plist_path);
+/// Environment variable naming a code signing identity (e.g. a Developer ID certificate) for [`patch`] to sign with
+/// instead of ad hoc.
+pub const SIGN_IDENTITY_ENV: &str = "DAYSTROM_SIGN_IDENTITY";
+
+/// Environment variable naming an entitlements plist for [`patch`] to sign with instead of the generated one.
+pub const ENTITLEMENTS_FILE_ENV: &str = "DAYSTROM_ENTITLEMENTS_FILE";
 
+/// Check that `identity` matches a valid code signing identity in the keychain, via `security find-identity`.
+fn validate_identity(identity: &str) -> Result<(), String> {
+    let output = Command::new("security")
+        .args(["find-identity", "-v", "-p", "codesigning"])
+        .output()
+        .map_err(|e| format!("Could not run security: {e}"))?;
+    if identity_listed(&String::from_utf8_lossy(&output.stdout), identity) {
+        Ok(())
+    } else {
+        Err(format!("Signing identity not found in the keychain: {identity}"))
+    }
+}
+
+/// Whether `identity` matches an entry of `security find-identity` output, either by its SHA-1 hash or, like
+/// `codesign`, by a substring of its name.
+///
+/// Entries look like `  1) 0123...CDEF "Developer ID Application: Jane Doe (TEAMID)"`.
+fn identity_listed(listing: &str, identity: &str) -> bool {
+    listing
+        .lines()
+        .filter_map(|line| {
+            let (_, entry) = line.trim().split_once(") ")?;
+            let (hash, name) = entry.split_once(' ')?;
+            Some((hash, name.trim().trim_matches('"')))
+        })
+        .any(|(hash, name)| hash.eq_ignore_ascii_case(identity) || name.contains(identity))
+}
+
+/// Availability of the tools entitlement patching depends on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct Prerequisites {
+    /// Whether `codesign` can be run at all.
+    pub codesign_available: bool,
+    /// Active developer directory reported by `xcode-select -p`, if the command line tools are set up.
+    pub developer_dir: Option<String>,
+    /// Version of the installed Xcode command line tools package, if found.
+    pub tools_version: Option<String>,
+}
+
+/// Path fragments of locations where `codesign --force` cannot rewrite a signature: System Integrity Protection covers
+/// the system directories, and App Translocation runs quarantined apps from a read-only copy.
+const PROTECTED_PATH_PARTS: [&str; 5] = ["/System/", "/usr/bin/", "/bin/", "/sbin/", "/AppTranslocation/"];
+
+/// Whether `path` lies in a location listed in [`PROTECTED_PATH_PARTS`].
+fn is_protected_path(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    PROTECTED_PATH_PARTS.iter().any(|part| path.starts_with(part) || (part.starts_with("/App") && path.contains(part)))
+}
+
+/// Whether [`patch`] can rewrite the signature of the game executable, checked before trying.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct PatchReadiness {
+    /// Whether the executable can be opened for writing by the current user.
+    pub executable_writable: bool,
+    /// Whether the executable lies in a protected location, see [`PROTECTED_PATH_PARTS`]; the game has to be moved.
+    pub protected_location: bool,
+    /// Whether `codesign` can be run at all.
+    pub codesign_available: bool,
+    /// Whether all checks passed.
+    pub ready: bool,
+}
+
+/// Check whether [`patch`] can work on `executable`, so the UI can ask the user to move the game or install the
+/// command line tools instead of failing with an opaque `codesign` error.
+///
+/// Writability is tested by opening the executable for writing, without changing it.
+pub fn patch_readiness(executable: &Path) -> PatchReadiness {
+    let executable_writable = match fs::OpenOptions::new().write(true).open(executable) {
+        Ok(_) => true,
+        Err(e) => {
+            log_debug!("{} is not writable: {e}", executable.display());
+            false
+        }
+    };
+    let protected_location = is_protected_path(executable);
+    let codesign_available = Command::new("codesign").output().is_ok();
+
+    let readiness = PatchReadiness {
+        executable_writable,
+        protected_location,
+        codesign_available,
+        ready: executable_writable && !protected_location && codesign_available,
+    };
+    log_info!("Patch readiness: {readiness:?}");
+    readiness
+}
+
+/// Package ID of the Xcode command line tools, as known to `pkgutil`.
+const CLT_PACKAGE_ID: &str = "com.apple.pkg.CLTools_Executables";
+
+/// Check whether `codesign` and the Xcode command line tools are available, so the UI can ask the user to install
+/// them before [`check`] or [`patch`] fail with an opaque error.
+pub fn prerequisites() -> Prerequisites {
+    // codesign has no version flag; without arguments it prints its usage and exits non-zero, which is fine here
+    let codesign_available = match Command::new("codesign").output() {
+        Ok(_) => true,
+        Err(e) => {
+            log_warn!("codesign is not runnable: {e}");
+            false
+        }
+    };
+    let developer_dir = command_stdout("xcode-select", &["-p"]);
+    let tools_version = command_stdout("pkgutil", &[&format!("--pkg-info={CLT_PACKAGE_ID}")])
+        .and_then(|info| parse_pkg_version(&info));
+
+    let prerequisites = Prerequisites { codesign_available, developer_dir, tools_version };
+    log_info!("Prerequisites: {prerequisites:?}");
+    prerequisites
+}
+
+/// Run `program` with `args` and return its trimmed stdout, or `None` if it cannot be run or fails.
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| log_debug!("Could not run {program}: {e}"))
+        .ok()?;
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        log_error!("codesign failed: {stderr}");
-        return Err("Entitlement patching failed (see log for details)".to_string());
+        log_debug!("{program} exited with {}", output.status);
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+/// Extract the `version:` line from `pkgutil --pkg-info` output.
+fn parse_pkg_version(info: &str) -> Option<String> {
+    info.lines()
+        .find_map(|line| line.strip_prefix("version:"))
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+/// Changes [`patch`] would make to the code signature, computed by [`plan`] without touching the executable.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct PatchPlan {
+    /// Entitlement keys not present in the current signature.
+    pub added: Vec<String>,
+    /// Entitlement keys present but not `true`, which would be switched on.
+    pub enabled: Vec<String>,
+    /// Entitlement keys that are already granted.
+    pub unchanged: Vec<String>,
+    /// The entitlements plist that would be passed to `codesign`.
+    pub plist: String,
+}
+
+impl PatchPlan {
+    /// Build the plan from the current state of each key in the generated plist.
+    fn from_states<'a>(states: impl IntoIterator<Item = (&'a str, EntitlementState)>, plist: String) -> Self {
+        let mut plan = PatchPlan { added: vec![], enabled: vec![], unchanged: vec![], plist };
+        for (key, state) in states {
+            let list = match state {
+                EntitlementState::Absent => &mut plan.added,
+                EntitlementState::Denied => &mut plan.enabled,
+                EntitlementState::Granted => &mut plan.unchanged,
+            };
+            list.push(key.to_string());
+        }
+        plan
+    }
+}
+
+/// Work out what [`patch`] would change for the required entitlements plus any `extra` keys, without signing.
+///
+/// Uses [`check`] for the required entitlements and only reads the signature again when `extra` adds keys of its
+/// own. Returns an error if the signature could not be inspected.
+pub fn plan(executable: &Path, extra: &[&str]) -> Result<PatchPlan, String> {
+    let status = check(executable)?;
+    let mut states: Vec<(&str, EntitlementState)> = status.states.into_iter().collect();
+
+    let mut own: Vec<&str> = extra.iter().copied().filter(|key| !REQUIRED.contains(key)).collect();
+    own.dedup();
+    if !own.is_empty() {
+        let xml = read_entitlements_xml(executable)?;
+        for key in own {
+            if !states.iter().any(|(k, _)| *k == key) {
+                states.push((key, entitlement_state(&xml, key)));
+            }
+        }
+    }
+
+    let plan = PatchPlan::from_states(states, entitlements_plist(extra));
+    log_debug!(
+        "Patch plan: {} to add, {} to enable, {} unchanged",
+        plan.added.len(),
+        plan.enabled.len(),
+        plan.unchanged.len()
+    );
+    Ok(plan)
+}
+
+/// Read the modification time of `path`.
+fn read_mtime(path: &Path) -> io::Result<SystemTime> {
+    fs::metadata(path)?.modified()
+}
+
+/// Set the modification time of `path`, leaving its contents untouched.
+fn write_mtime(path: &Path, mtime: SystemTime) -> io::Result<()> {
+    fs::File::options().write(true).open(path)?.set_modified(mtime)
+}
+
+/// Run `f`, which rewrites `path` (e.g. re-signs it), and set the modification time back to what it was before.
+///
+/// The monitor takes a changed modification time of the executable for a game update, so [`patch`] and [`restore`]
+/// must not leave one behind. Failing to read or restore the time is only logged.
+fn preserving_mtime<T>(path: &Path, f: impl FnOnce() -> T) -> T {
+    let mtime = read_mtime(path)
+        .map_err(|e| log_warn!("Could not read modification time, it will not be preserved: {e}"))
+        .ok();
+    let result = f();
+    if let Some(mtime) = mtime {
+        if let Err(e) = write_mtime(path, mtime) {
+            log_warn!("Could not restore modification time: {e}");
+        }
+    }
+    result
+}
+
+/// Step of [`patch`] reported to the optional progress callback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum PatchStage {
+    /// Saving the current entitlements for a later restore.
+    BackingUp,
+    /// Removing updater leftovers from the app bundle.
+    CleaningBundle,
+    /// Generating the entitlements plist.
+    WritingPlist,
+    /// Re-signing the executable.
+    RunningCodesign,
+    /// Checking the new signature.
+    Verifying,
+}
+
+/// Re-sign the game executable with the four required entitlements for mod injection, plus any `extra` keys.
+///
+/// Signs with the code signing `identity` if given, after checking it exists in the keychain, and ad hoc otherwise.
+/// Backs up the current entitlements and cleans up leftover temp files from the Scopely updater first, then
+/// signs with the generated entitlements and verifies the result. The original modification time is restored after
+/// signing, since some anti-tamper checks look at it. The new signature must pass `codesign --verify` (see
+/// [`verify_signature`]), whose output is returned on failure. Only the required entitlements are verified; `extra`
+/// keys (e.g. `com.apple.security.cs.allow-jit` for experimental mods) are granted on a best-effort basis.
+/// If `entitlements_file` is given, that plist is signed in as is instead of the generated one (and `extra` is
+/// ignored); it must be well-formed and grant all required keys, otherwise nothing is changed.
+/// If given, `progress` is called with each [`PatchStage`] as it starts; the outcome is only reported via the result.
+/// Signing and verification failures are returned as [`GameError::Codesign`].
+pub fn patch(
+    executable: &Path,
+    extra: &[&str],
+    entitlements_file: Option<&Path>,
+    identity: Option<&str>,
+    progress: Option<&dyn Fn(PatchStage)>,
+) -> Result<(), GameError> {
+    let report = |stage: PatchStage| {
+        log_debug!("Patch stage: {stage:?}");
+        if let Some(progress) = progress {
+            progress(stage);
+        }
+    };
+    log_info!("Patching entitlements on {}", executable.display());
+
+    if let Some(identity) = identity {
+        validate_identity(identity).map_err(|e| {
+            log_error!("{e}");
+            GameError::Codesign(e)
+        })?;
+        log_info!("Signing with identity {identity}");
+    }
+
+    let supplied = entitlements_file.map(|path| {
+        let xml = read_entitlements_file(path).map_err(|e| {
+            log_error!("{e}");
+            GameError::Failed(e)
+        })?;
+        Ok::<_, GameError>((path, xml))
+    }).transpose()?;
+
+    // Keep the original entitlements around for restore; a failed backup must not block patching
+    report(PatchStage::BackingUp);
+    if let Err(e) = backup(executable) {
+        log_warn!("Continuing without entitlements backup: {e}");
+    }
+
+    // Clean up Scopely updater leftovers that would make codesign fail
+    report(PatchStage::CleaningBundle);
+    clean_bundle_temp_files(executable);
+
+    report(PatchStage::WritingPlist);
+    let plist = match supplied {
+        Some((path, plist)) => {
+            if !extra.is_empty() {
+                log_warn!("Ignoring additional entitlements, using {} as is", path.display());
+            }
+            log_info!("Using entitlements from {}", path.display());
+            plist
+        }
+        None => {
+            if !extra.is_empty() {
+                log_info!("Additional entitlements: {}", extra.join(", "));
+            }
+            entitlements_plist(extra)
+        }
+    };
+
+    report(PatchStage::RunningCodesign);
+    if let Err(e) = preserving_mtime(executable, || sign(executable, Some(&plist), identity)) {
+        log_error!("codesign failed: {e}");
+        return Err(GameError::Codesign("Entitlement patching failed (see log for details)".to_string()));
     }
 
-    // Verify the patch worked
-    let status = check(executable);
+    // Verify the patch worked: a valid signature first, then the entitlements
+    report(PatchStage::Verifying);
+    log_debug!("Verifying signature of {}", executable.display());
+    if let Err(e) = verify_signature(executable) {
+        log_error!("Signature verification failed: {e}");
+        return Err(GameError::Codesign(format!("Signature verification failed after patching: {e}")));
+    }
+    let status = check(executable).map_err(|e| {
+        log_error!("Could not verify entitlements after patch: {e}");
+        GameError::Codesign("Entitlement patching could not be verified (see log for details)".to_string())
+    })?;
     if status.all_granted() {
         log_info!("Entitlements patched successfully");
         Ok(())
     } else {
-        let names: Vec<_> = status.missing.iter()
-            .map(|k| k.strip_prefix("com.apple.security.").unwrap_or(k))
+        log_error!("Entitlements still missing after patch: {}", status.missing_short().join(", "));
+        Err(GameError::Codesign("Entitlement patching incomplete (see log for details)".to_string()))
+    }
+}
+
+// ---- Tests ----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>com.apple.security.cs.allow-dyld-environment-variables</key>
+    <true/>
+    <key>com.apple.security.cs.allow-unsigned-executable-memory</key>
+    <true/>
+    <key>com.apple.security.cs.disable-library-validation</key>
+    <true/>
+    <key>com.apple.security.get-task-allow</key>
+    <true/>
+</dict>
+</plist>"#;
+
+    #[test]
+    fn missing_short_strips_prefix() {
+        let status = EntitlementStatus {
+            missing: vec!["com.apple.security.get-task-allow", "com.example.custom"],
+            states: BTreeMap::new(),
+        };
+        assert_eq!(status.missing_short(), ["get-task-allow", "com.example.custom"]);
+    }
+
+    #[test]
+    fn granted_short_lists_granted_states() {
+        let status = EntitlementStatus::from_states(BTreeMap::from([
+            ("com.apple.security.cs.disable-library-validation", EntitlementState::Granted),
+            ("com.apple.security.get-task-allow", EntitlementState::Denied),
+        ]));
+        assert_eq!(status.granted_short(), ["cs.disable-library-validation"]);
+    }
+
+    #[test]
+    fn entitlement_state_present_and_true() {
+        assert_eq!(
+            entitlement_state(FULL_PLIST, "com.apple.security.cs.allow-dyld-environment-variables"),
+            EntitlementState::Granted,
+        );
+    }
+
+    #[test]
+    fn entitlement_state_present_but_false() {
+        let xml = r#"<dict>
+    <key>com.apple.security.get-task-allow</key>
+    <false/>
+</dict>"#;
+        assert_eq!(entitlement_state(xml, "com.apple.security.get-task-allow"), EntitlementState::Denied);
+    }
+
+    #[test]
+    fn entitlement_state_missing_key() {
+        assert_eq!(entitlement_state(FULL_PLIST, "com.apple.security.app-sandbox"), EntitlementState::Absent);
+    }
+
+    #[test]
+    fn entitlement_state_empty_xml() {
+        assert_eq!(entitlement_state("", "com.apple.security.get-task-allow"), EntitlementState::Absent);
+    }
+
+    #[test]
+    fn entitlement_state_key_without_value() {
+        let xml = "<dict><key>com.apple.security.get-task-allow</key></dict>";
+        assert_eq!(entitlement_state(xml, "com.apple.security.get-task-allow"), EntitlementState::Denied);
+    }
+
+    #[test]
+    fn backup_path_next_to_app_bundle() {
+        let exe = Path::new("/Games/STFC/Star Trek Fleet Command.app/Contents/MacOS/Star Trek Fleet Command");
+        assert_eq!(backup_path(exe), Some(PathBuf::from("/Games/STFC").join(BACKUP_FILE_NAME)));
+    }
+
+    #[test]
+    fn entitlement_state_tolerates_whitespace_variants() {
+        // Value on same line as key
+        let xml = "<key>com.apple.security.get-task-allow</key><true/>";
+        assert_eq!(entitlement_state(xml, "com.apple.security.get-task-allow"), EntitlementState::Granted);
+
+        // Extra whitespace / newlines between key and value
+        let xml = "<key>com.apple.security.get-task-allow</key>\n\t\t<true/>";
+        assert_eq!(entitlement_state(xml, "com.apple.security.get-task-allow"), EntitlementState::Granted);
+    }
+
+    #[test]
+    fn status_from_states_keeps_missing_semantics() {
+        let mut states: BTreeMap<_, _> = REQUIRED.iter().map(|key| (*key, EntitlementState::Granted)).collect();
+        assert!(EntitlementStatus::from_states(states.clone()).all_granted());
+
+        states.insert("com.apple.security.get-task-allow", EntitlementState::Denied);
+        let status = EntitlementStatus::from_states(states);
+        assert!(!status.all_granted());
+        assert_eq!(status.missing, ["com.apple.security.get-task-allow"]);
+    }
+
+    #[test]
+    fn entitlements_plist_default_matches_required_set() {
+        assert_eq!(entitlements_plist(&[]), FULL_PLIST);
+    }
+
+    #[test]
+    fn entitlements_plist_with_extra_keys() {
+        let extra = "com.apple.security.cs.allow-jit";
+        let xml = entitlements_plist(&[extra, REQUIRED[0], extra]);
+
+        let keys: Vec<_> = xml
+            .split("<key>")
+            .skip(1)
+            .map(|rest| rest.split_once("</key>").unwrap().0)
             .collect();
-        log_error!("Entitlements still missing after patch: {}", names.join(", "));
-        Err("Entitlement patching incomplete (see log for details)".to_string())
+        let mut expected = REQUIRED.to_vec();
+        expected.push(extra);
+        assert_eq!(keys, expected);
+
+        for key in &keys {
+            assert_eq!(entitlement_state(&xml, key), EntitlementState::Granted);
+        }
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.ends_with("</dict>\n</plist>"));
+        assert_eq!(xml.matches("<dict>").count(), 1);
+        assert_eq!(xml.matches("</dict>").count(), 1);
+        assert_eq!(xml.matches("<true/>").count(), keys.len());
     }
+
+    #[test]
+    fn supplied_plist_is_validated() {
+        assert_eq!(validate_entitlements_plist(FULL_PLIST), Ok(()));
+        assert_eq!(validate_entitlements_plist(&entitlements_plist(&["com.apple.security.cs.allow-jit"])), Ok(()));
+
+        let commented = FULL_PLIST.replace("<dict>", "<dict>\n    <!-- team <override> -->");
+        assert_eq!(validate_entitlements_plist(&commented), Ok(()));
+
+        let unclosed = FULL_PLIST.replace("</dict>", "");
+        assert!(validate_entitlements_plist(&unclosed).is_err());
+        assert!(validate_entitlements_plist("not a plist").is_err());
+    }
+
+    #[test]
+    fn supplied_plist_missing_required_key_is_rejected() {
+        let missing = FULL_PLIST.replace("<key>com.apple.security.get-task-allow</key>\n    <true/>\n", "");
+        assert_eq!(
+            validate_entitlements_plist(&missing),
+            Err("Required entitlements not granted: get-task-allow".to_string()),
+        );
+
+        let denied = FULL_PLIST.replace(
+            "<key>com.apple.security.cs.disable-library-validation</key>\n    <true/>",
+            "<key>com.apple.security.cs.disable-library-validation</key>\n    <false/>",
+        );
+        assert_eq!(
+            validate_entitlements_plist(&denied),
+            Err("Required entitlements not granted: cs.disable-library-validation".to_string()),
+        );
+    }
+
+    #[test]
+    fn entitlements_plist_escapes_keys() {
+        let xml = entitlements_plist(&["a<b>&c"]);
+        assert!(xml.contains("<key>a&lt;b&gt;&amp;c</key>"));
+    }
+
+    #[test]
+    fn patch_plan_sorts_keys_by_state() {
+        let states = [
+            (REQUIRED[0], EntitlementState::Granted),
+            (REQUIRED[1], EntitlementState::Denied),
+            (REQUIRED[2], EntitlementState::Absent),
+            ("com.apple.security.cs.allow-jit", EntitlementState::Absent),
+        ];
+        let plan = PatchPlan::from_states(states, entitlements_plist(&[]));
+        assert_eq!(plan.added, [REQUIRED[2], "com.apple.security.cs.allow-jit"]);
+        assert_eq!(plan.enabled, [REQUIRED[1]]);
+        assert_eq!(plan.unchanged, [REQUIRED[0]]);
+        assert_eq!(plan.plist, FULL_PLIST);
+    }
+
+    #[test]
+    fn mtime_round_trip() {
+        let dir = std::env::temp_dir().join("daystrom_test_mtime");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("prime");
+        fs::write(&file, "binary").unwrap();
+
+        let original = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        write_mtime(&file, original).unwrap();
+        fs::write(&file, "re-signed binary").unwrap();
+        assert_ne!(read_mtime(&file).unwrap(), original);
+
+        write_mtime(&file, original).unwrap();
+        assert_eq!(read_mtime(&file).unwrap(), original);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "re-signed binary");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn preserving_mtime_restores_time_after_rewrite() {
+        let dir = std::env::temp_dir().join("daystrom_test_preserving_mtime");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("prime");
+        fs::write(&file, "binary").unwrap();
+        let original = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        write_mtime(&file, original).unwrap();
+
+        let result = preserving_mtime(&file, || fs::write(&file, "restored binary"));
+
+        assert!(result.is_ok());
+        assert_eq!(read_mtime(&file).unwrap(), original);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "restored binary");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn pkg_version_from_pkgutil_output() {
+        let info = "package-id: com.apple.pkg.CLTools_Executables\nversion: 15.3.0.0.1.1708646388\n\
+                    volume: /\nlocation: /\ninstall-time: 1710000000\n";
+        assert_eq!(parse_pkg_version(info).as_deref(), Some("15.3.0.0.1.1708646388"));
+        assert_eq!(parse_pkg_version("No receipt for 'com.apple.pkg.CLTools_Executables' found at '/'."), None);
+    }
+
+    #[test]
+    fn transient_codesign_error_resource_busy() {
+        assert!(is_transient_codesign_error(
+            "/Games/STFC/prime.app/Contents/MacOS/prime: Resource busy\n"
+        ));
+    }
+
+    #[test]
+    fn transient_codesign_error_genuine_failure() {
+        assert!(!is_transient_codesign_error("prime: code object is not signed at all\n"));
+        assert!(!is_transient_codesign_error("prime: invalid or unsupported format for signature\n"));
+    }
+
+    #[test]
+    fn identity_listed_by_name_or_hash() {
+        let listing = concat!(
+            "  1) 0123456789ABCDEF0123456789ABCDEF01234567 \"Developer ID Application: Jane Doe (TEAM123456)\"\n",
+            "     1 valid identities found\n",
+        );
+        assert!(identity_listed(listing, "Developer ID Application: Jane Doe (TEAM123456)"));
+        assert!(identity_listed(listing, "Jane Doe"));
+        assert!(identity_listed(listing, "0123456789abcdef0123456789abcdef01234567"));
+        assert!(!identity_listed(listing, "John Roe"));
+        assert!(!identity_listed(listing, "valid identities"));
+    }
+
+    #[test]
+    fn protected_paths() {
+        assert!(is_protected_path(Path::new("/System/Applications/Game.app/Contents/MacOS/Game")));
+        assert!(is_protected_path(Path::new(
+            "/private/var/folders/ab/T/AppTranslocation/1234/d/Game.app/Contents/MacOS/Game"
+        )));
+        assert!(!is_protected_path(Path::new("/Applications/Game.app/Contents/MacOS/Game")));
+        assert!(!is_protected_path(Path::new("/Users/jane/Games/Game.app/Contents/MacOS/Game")));
+        assert!(!is_protected_path(Path::new("/usr/local/games/Game.app/Contents/MacOS/Game")));
+    }
+
 }
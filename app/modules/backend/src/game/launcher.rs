@@ -1,43 +1,590 @@
-use std::path::Path;
-use std::process::Command;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use super::GameInfo;
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::{GameError, GameInfo};
 use crate::use_log;
 
 use_log!("Launcher");
 
+/// File name for the captured game stdout/stderr, stored in the log directory.
+const GAME_OUTPUT_FILE: &str = "game-stdout.log";
+
+/// Maximum time [`relaunch`] waits for the stopped game process to exit.
+const RELAUNCH_EXIT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Interval between process checks while [`relaunch`] waits for the game to exit.
+const RELAUNCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
 /// Path to the Scopely launcher application on macOS.
 #[cfg(target_os = "macos")]
 const LAUNCHER_APP: &str = "/Applications/Star Trek Fleet Command.app";
 
+/// Environment variable selecting how [`launch`] starts the game on macOS, see [`LaunchMode`].
+#[cfg(target_os = "macos")]
+const LAUNCH_MODE_ENV: &str = "DAYSTROM_LAUNCH_MODE";
+
+/// How [`launch`] starts the game on macOS.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LaunchMode {
+    /// Spawn the executable inside the `.app` bundle directly (the default).
+    Direct,
+    /// Start the `.app` bundle through LaunchServices via `open -a`, passing the DYLD variables with `--env`.
+    ///
+    /// Avoids problems with system integrations that expect a regular bundle launch. Caveats: the variables only
+    /// arrive if `open` supports `--env` (older systems drop them, and the game starts unmodded), the working
+    /// directory is chosen by LaunchServices, and the game output can only go to [`GAME_OUTPUT_FILE`], not be
+    /// streamed live.
+    Bundle,
+}
+
+/// Resolve the launch mode from the raw [`LAUNCH_MODE_ENV`] value.
+///
+/// Falls back to [`LaunchMode::Direct`] when the variable is unset or unrecognized.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn launch_mode(value: Option<&str>) -> LaunchMode {
+    match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        Some("bundle") => LaunchMode::Bundle,
+        _ => LaunchMode::Direct,
+    }
+}
+
+/// Build the base command for starting the game: executable, working directory, and extra arguments.
+///
+/// Platform-specific injection (e.g. DYLD environment variables) is added by the caller. Unused on Linux, where
+/// launching is not supported.
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn game_command(game: &GameInfo, args: &[String]) -> Command {
+    let mut cmd = Command::new(&game.executable);
+    cmd.current_dir(&game.install_dir).args(args);
+    cmd
+}
+
+/// Build the `open` command for [`LaunchMode::Bundle`]: start `bundle` as a new instance with `env` set and `args`
+/// passed through, writing stdout and stderr to `output` if given.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn bundle_command(bundle: &Path, env: &[(&str, &OsStr)], args: &[String], output: Option<&Path>) -> Command {
+    let mut cmd = Command::new("open");
+    cmd.arg("-n").arg("-a").arg(bundle);
+    for (key, value) in env {
+        let mut pair = OsString::from(format!("{key}="));
+        pair.push(value);
+        cmd.arg("--env").arg(pair);
+    }
+    if let Some(output) = output {
+        cmd.arg("--stdout").arg(output).arg("--stderr").arg(output);
+    }
+    if !args.is_empty() {
+        cmd.arg("--args").args(args);
+    }
+    cmd
+}
+
+/// Start the game's `.app` bundle via [`bundle_command`] and wait for `open` to hand it over to LaunchServices.
+///
+/// Live output is not available this way, so `on_output` only gets a warning logged.
+#[cfg(target_os = "macos")]
+fn launch_bundle(
+    game: &GameInfo,
+    env: &[(&str, &OsStr)],
+    args: &[String],
+    on_output: Option<OutputSink>,
+) -> Result<(), GameError> {
+    let bundle = game
+        .executable
+        .ancestors()
+        .nth(3)
+        .filter(|path| path.extension().is_some_and(|ext| ext == "app"))
+        .ok_or_else(|| format!("Could not determine the app bundle of {}", game.executable.display()))?;
+    if on_output.is_some() {
+        log_warn!("Live game output is not available when launching the app bundle");
+    }
+
+    let output = open_output_file().map(|(path, _)| path);
+    let result = bundle_command(bundle, env, args, output.as_deref()).output().map_err(|e| {
+        log_error!("Could not run open: {e}");
+        GameError::Failed("Failed to launch game (see log for details)".to_string())
+    })?;
+    if !result.status.success() {
+        log_error!("open failed ({}): {}", result.status, String::from_utf8_lossy(&result.stderr).trim());
+        return Err(GameError::Failed("Failed to launch game (see log for details)".to_string()));
+    }
+
+    match output {
+        Some(path) => log_info!("Game bundle opened, output captured in {}", path.display()),
+        None => log_info!("Game bundle opened"),
+    }
+    Ok(())
+}
+
+/// Redirect the game's stdout and stderr into [`GAME_OUTPUT_FILE`] in the log directory.
+///
+/// The file is truncated on each launch. Returns the file path, or `None` (with warn logging) if the log directory is
+/// unavailable or the file cannot be opened — the game then inherits our stdio as before.
+fn redirect_output(cmd: &mut Command) -> Option<PathBuf> {
+    let (path, file) = open_output_file()?;
+    match file.try_clone() {
+        Ok(stdout) => {
+            cmd.stdout(Stdio::from(stdout)).stderr(Stdio::from(file));
+            Some(path)
+        }
+        Err(e) => {
+            log_warn!("Could not open {} for game output: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Create (or truncate) [`GAME_OUTPUT_FILE`] in the log directory, returning its path and handle.
+///
+/// Returns `None` (with warn logging) if the log directory is unavailable or the file cannot be created.
+fn open_output_file() -> Option<(PathBuf, fs::File)> {
+    let dir = crate::logging::log_dir()?;
+    let path = dir.join(GAME_OUTPUT_FILE);
+    match fs::create_dir_all(&dir).and_then(|()| fs::File::create(&path)) {
+        Ok(file) => Some((path, file)),
+        Err(e) => {
+            log_warn!("Could not open {} for game output: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// A line of game output, forwarded live while the game runs if [`launch`] is given an [`OutputSink`].
+///
+/// stdout and stderr share [`GAME_OUTPUT_FILE`], so lines are not attributed to either stream.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct GameOutputLine {
+    /// The line without its line ending.
+    pub line: String,
+}
+
+/// Receiver for live game output, called once per line from the thread started by [`tail_output`].
+pub type OutputSink = Arc<dyn Fn(GameOutputLine) + Send + Sync>;
+
+/// Interval at which [`tail_output`] looks for new game output once it has caught up with the file.
+const TAIL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Spawn the game process without waiting for it.
+///
+/// stdout and stderr are always captured via [`redirect_output`], so the game never holds a pipe to Project Daystrom
+/// and keeps running undisturbed when it quits. With a `sink`, the output file is also followed and forwarded line by
+/// line (see [`tail_output`]).
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn spawn_game(cmd: &mut Command, sink: Option<OutputSink>) -> Result<(), GameError> {
+    let output = redirect_output(cmd);
+
+    let child = cmd.spawn().map_err(|e| {
+        log_error!("Failed to spawn game process: {e}");
+        GameError::Failed("Failed to launch game (see log for details)".to_string())
+    })?;
+    match (sink, &output) {
+        (Some(sink), Some(path)) => tail_output(child, path, sink),
+        (Some(_), None) => log_warn!("Game output is not captured, it cannot be streamed live"),
+        (None, _) => {}
+    }
+
+    log_spawned(output.as_deref());
+    Ok(())
+}
+
+/// Follow `path` on a background thread and pass each new line to `sink` until `child` has exited, see
+/// [`tail_lines`].
+fn tail_output(mut child: Child, path: &Path, sink: OutputSink) {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            log_warn!("Could not follow {} for live game output: {e}", path.display());
+            return;
+        }
+    };
+    thread::spawn(move || {
+        let exited = || {
+            let exited = !matches!(child.try_wait(), Ok(None));
+            if !exited {
+                thread::sleep(TAIL_POLL_INTERVAL);
+            }
+            exited
+        };
+        tail_lines(BufReader::new(file), exited, sink.as_ref());
+    });
+}
+
+/// Pass each line read from `reader` to `sink`. At end of input, `exited` is asked whether the writer is done
+/// (waiting a little if not); after it says so, the rest is read and the loop ends.
+///
+/// A line is only passed on once its line ending arrived, except for an unterminated last line. Invalid UTF-8 is
+/// replaced rather than ending the stream.
+fn tail_lines(
+    mut reader: impl BufRead,
+    mut exited: impl FnMut() -> bool,
+    sink: &(dyn Fn(GameOutputLine) + Send + Sync),
+) {
+    let emit = |buf: &[u8]| {
+        let line = String::from_utf8_lossy(buf).trim_end_matches(['\r', '\n']).to_string();
+        sink(GameOutputLine { line });
+    };
+    let mut buf = Vec::new();
+    let mut finishing = false;
+    loop {
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) if finishing => break,
+            // Caught up: once the writer is gone, one more pass picks up what it wrote last
+            Ok(0) => finishing = exited(),
+            Ok(_) if buf.ends_with(b"\n") => {
+                emit(&buf);
+                buf.clear();
+            }
+            Ok(_) => {} // partial line, the rest has not been written yet
+            Err(_) => break,
+        }
+    }
+    if !buf.is_empty() {
+        emit(&buf);
+    }
+}
+
+/// Log the successful spawn, including where the game output goes.
+fn log_spawned(output: Option<&Path>) {
+    match output {
+        Some(path) => log_info!("Game process spawned, output captured in {}", path.display()),
+        None => log_info!("Game process spawned"),
+    }
+}
+
+/// Build the `DYLD_LIBRARY_PATH` value: the `extra` search paths in the given order, followed by the mod library's
+/// own directory `lib_dir`, joined with colons.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn dyld_library_path(extra: &[PathBuf], lib_dir: &Path) -> OsString {
+    let mut value = OsString::new();
+    for dir in extra.iter().map(PathBuf::as_path).chain([lib_dir]) {
+        if !value.is_empty() {
+            value.push(":");
+        }
+        value.push(dir);
+    }
+    value
+}
+
+/// Split the output of `lipo -archs` into architecture names (e.g. `["x86_64", "arm64"]`).
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_archs(output: &str) -> Vec<&str> {
+    output.split_whitespace().collect()
+}
+
+/// Pick the architecture the game will actually run as.
+///
+/// A universal binary runs natively if it contains the host architecture; otherwise it falls back to its first slice
+/// (e.g. `x86_64` under Rosetta).
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn runtime_arch<'a>(game_archs: &[&'a str], host_arch: &str) -> Option<&'a str> {
+    game_archs
+        .iter()
+        .find(|arch| **arch == host_arch)
+        .or_else(|| game_archs.first())
+        .copied()
+}
+
+/// Map Rust's [`std::env::consts::ARCH`] to the name used by `lipo`.
+#[cfg(target_os = "macos")]
+fn host_arch() -> &'static str {
+    match std::env::consts::ARCH {
+        "aarch64" => "arm64",
+        other => other,
+    }
+}
+
+/// Run `lipo -archs` on a Mach-O file and return the contained architectures.
+#[cfg(target_os = "macos")]
+fn read_archs(path: &Path) -> Result<Vec<String>, String> {
+    let out = Command::new("lipo").arg("-archs").arg(path).output().map_err(|e| {
+        log_error!("Could not run lipo: {e}");
+        "Failed to check the mod library architecture (see log for details)".to_string()
+    })?;
+    if !out.status.success() {
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        log_error!("lipo failed for {} ({}): {stderr}", path.display(), out.status);
+        return Err("Failed to check the mod library architecture (see log for details)".to_string());
+    }
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    Ok(parse_archs(&stdout).into_iter().map(String::from).collect())
+}
+
+/// Extended attribute macOS sets on downloaded files; Gatekeeper can make DYLD refuse to load a tagged library.
+#[cfg(target_os = "macos")]
+const QUARANTINE_ATTR: &str = "com.apple.quarantine";
+
+/// Check whether `path` carries the extended attribute `name`, via `xattr -p`.
+///
+/// Returns `false` if the attribute is absent, the file does not exist, or `xattr` cannot be run.
+#[cfg(target_os = "macos")]
+fn has_xattr(path: &Path, name: &str) -> bool {
+    Command::new("xattr")
+        .arg("-p")
+        .arg(name)
+        .arg(path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Strip the Gatekeeper quarantine attribute from the mod library, if present.
+///
+/// Best effort: a failure is logged, and the launch goes ahead regardless.
+#[cfg(target_os = "macos")]
+fn remove_quarantine(mod_library: &Path) {
+    if !has_xattr(mod_library, QUARANTINE_ATTR) {
+        log_debug!("No quarantine attribute on {}", mod_library.display());
+        return;
+    }
+    match Command::new("xattr").arg("-d").arg(QUARANTINE_ATTR).arg(mod_library).output() {
+        Ok(out) if out.status.success() => log_debug!("Removed quarantine attribute from {}", mod_library.display()),
+        Ok(out) => {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            log_debug!("Could not remove quarantine attribute ({}): {}", out.status, stderr.trim());
+        }
+        Err(e) => log_debug!("Could not run xattr: {e}"),
+    }
+}
+
+/// Verify that the mod library contains a slice for the architecture the game will run as.
+///
+/// Without this check, DYLD silently skips an incompatible library and the game starts unmodded.
+#[cfg(target_os = "macos")]
+fn check_architecture(executable: &Path, mod_library: &Path) -> Result<(), String> {
+    let game_archs = read_archs(executable)?;
+    let lib_archs = read_archs(mod_library)?;
+    let game_refs: Vec<&str> = game_archs.iter().map(String::as_str).collect();
+    let Some(arch) = runtime_arch(&game_refs, host_arch()) else {
+        return Err(format!("Could not determine the architecture of {}", executable.display()));
+    };
+
+    if lib_archs.iter().any(|a| a == arch) {
+        log_debug!("Mod library architectures {lib_archs:?} match game architecture {arch}");
+        return Ok(());
+    }
+    log_error!("Mod library architectures {lib_archs:?} do not include game architecture {arch}");
+    Err(format!(
+        "Mod library architecture ({}) does not match the game architecture ({arch})",
+        lib_archs.join(", ")
+    ))
+}
+
+/// Magic numbers of thin Mach-O headers (32/64 bit, either byte order).
+const MACHO_MAGICS: [[u8; 4]; 4] = [
+    [0xfe, 0xed, 0xfa, 0xce],
+    [0xce, 0xfa, 0xed, 0xfe],
+    [0xfe, 0xed, 0xfa, 0xcf],
+    [0xcf, 0xfa, 0xed, 0xfe],
+];
+
+/// Magic numbers of universal (fat) Mach-O headers with 32 and 64 bit offsets, always big-endian.
+const FAT_MAGIC: [u8; 4] = [0xca, 0xfe, 0xba, 0xbe];
+const FAT_MAGIC_64: [u8; 4] = [0xca, 0xfe, 0xba, 0xbf];
+
+/// Mach-O file type of a dynamic library (`MH_DYLIB`).
+const MH_DYLIB: u32 = 6;
+
+/// Check whether `file` is a Mach-O dynamic library, thin or universal.
+///
+/// A universal file is judged by its first slice. Returns `false` for anything that is not Mach-O.
+fn is_macho_dylib(file: &mut (impl Read + Seek)) -> io::Result<bool> {
+    let mut header = [0u8; 32];
+    let len = read_up_to(file, &mut header)?;
+    let magic: [u8; 4] = header[..4].try_into().expect("slice of length 4");
+    if len < 16 {
+        return Ok(false);
+    }
+    if magic == FAT_MAGIC || magic == FAT_MAGIC_64 {
+        let slice_offset = if magic == FAT_MAGIC {
+            u64::from(u32::from_be_bytes(header[16..20].try_into().expect("slice of length 4")))
+        } else if len >= 24 {
+            u64::from_be_bytes(header[16..24].try_into().expect("slice of length 8"))
+        } else {
+            return Ok(false);
+        };
+        file.seek(SeekFrom::Start(slice_offset))?;
+        let len = read_up_to(file, &mut header)?;
+        return Ok(len >= 16 && thin_macho_file_type(&header) == Some(MH_DYLIB));
+    }
+    Ok(thin_macho_file_type(&header) == Some(MH_DYLIB))
+}
+
+/// Return the file type of a thin Mach-O header, or `None` if `header` does not start with a Mach-O magic.
+fn thin_macho_file_type(header: &[u8]) -> Option<u32> {
+    let magic: [u8; 4] = header.get(..4)?.try_into().ok()?;
+    let file_type: [u8; 4] = header.get(12..16)?.try_into().ok()?;
+    match MACHO_MAGICS.iter().position(|m| *m == magic)? {
+        // feedface/feedfacf as read big-endian: the file is big-endian
+        0 | 2 => Some(u32::from_be_bytes(file_type)),
+        _ => Some(u32::from_le_bytes(file_type)),
+    }
+}
+
+/// Fill `buf` from `reader` as far as possible, returning the number of bytes read (less than its length at EOF).
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(len)
+}
+
+/// Outcome of [`validate_dylib`]: one flag per check, and a message for each check that failed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct DylibReport {
+    /// Whether the path exists and is a file.
+    pub exists: bool,
+    /// Whether the file is a Mach-O dynamic library.
+    pub is_dylib: bool,
+    /// Whether the library contains a slice for the architecture the game runs as.
+    pub arch_matches: bool,
+    /// Whether the file is free of the Gatekeeper quarantine attribute.
+    pub not_quarantined: bool,
+    /// Why checks failed, in the order of the flags above.
+    pub messages: Vec<String>,
+}
+
+impl DylibReport {
+    /// Whether all checks passed, so the library can be injected.
+    pub fn is_valid(&self) -> bool {
+        self.exists && self.is_dylib && self.arch_matches && self.not_quarantined
+    }
+}
+
+/// Check a user-supplied dylib before injecting it into the game started from `executable`.
+///
+/// Reuses the checks of [`launch`]: [`check_architecture`] against the game, and the quarantine attribute that
+/// [`remove_quarantine`] would strip. Later checks are skipped (and fail) once the file is missing or not a dylib.
+/// The architecture and quarantine checks need macOS; elsewhere they fail with a message.
+#[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+pub fn validate_dylib(path: &Path, executable: Option<&Path>) -> DylibReport {
+    let mut report = DylibReport::default();
+    if !path.is_file() {
+        report.messages.push(format!("{} does not exist or is not a file", path.display()));
+        return report;
+    }
+    report.exists = true;
+
+    match fs::File::open(path).and_then(|mut file| is_macho_dylib(&mut file)) {
+        Ok(true) => report.is_dylib = true,
+        Ok(false) => report.messages.push("The file is not a Mach-O dynamic library".to_string()),
+        Err(e) => report.messages.push(format!("Could not read the file: {e}")),
+    }
+    if !report.is_dylib {
+        return report;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        match executable {
+            Some(executable) => match check_architecture(executable, path) {
+                Ok(()) => report.arch_matches = true,
+                Err(e) => report.messages.push(e),
+            },
+            None => report.messages.push("The game is not installed, so its architecture is unknown".to_string()),
+        }
+        if has_xattr(path, QUARANTINE_ATTR) {
+            report.messages.push("The file is quarantined by Gatekeeper".to_string());
+        } else {
+            report.not_quarantined = true;
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    report.messages.push("Architecture and quarantine checks are only available on macOS".to_string());
+
+    report
+}
+
+/// Patch the entitlements of `game` with `patch` if any are missing, for an opt-in patch-on-launch.
+///
+/// Does nothing when all required entitlements are granted. The caller must make sure the game is not running.
+/// Returns the patch error if patching fails, so the launch is not attempted.
+#[cfg(target_os = "macos")]
+pub fn auto_patch(game: &GameInfo, patch: impl FnOnce(&Path) -> Result<(), GameError>) -> Result<(), GameError> {
+    let status = super::entitlements::check(&game.executable)
+        .map_err(|e| GameError::Failed(format!("Could not check entitlements: {e}")))?;
+    if status.all_granted() {
+        log_debug!("Auto-patch not needed, all entitlements granted");
+        return Ok(());
+    }
+    log_info!("Auto-patching missing entitlements before launch: {}", status.missing_short().join(", "));
+    patch(&game.executable).inspect_err(|e| log_error!("Auto-patch failed: {e}"))
+}
+
 /// Launch the game with the mod library injected via DYLD environment variables.
 ///
+/// `args` are passed through to the game executable unchanged, stdout/stderr are captured via [`redirect_output`].
+/// `library_paths` are searched before the mod library's own directory, for mods that depend on dylibs elsewhere.
+/// With `on_output`, the game output is also forwarded to it line by line, see [`spawn_game`].
 /// The child process is spawned but not awaited — the game runs independently of Project Daystrom.
-/// Returns an error if the game is already running or the process fails to spawn.
+/// A Gatekeeper quarantine attribute on the mod library is removed first (see [`remove_quarantine`]).
+/// [`LAUNCH_MODE_ENV`] selects whether the executable is spawned directly or the bundle is opened, see [`LaunchMode`].
+/// Returns an error if the game is already running, required entitlements are missing, the mod library lacks the
+/// game's architecture (see [`check_architecture`]), or the process fails to spawn.
 #[cfg(target_os = "macos")]
-pub fn launch(game: &GameInfo, mod_library: &Path) -> Result<(), String> {
+pub fn launch(
+    game: &GameInfo,
+    mod_library: &Path,
+    args: &[String],
+    library_paths: &[PathBuf],
+    on_output: Option<OutputSink>,
+) -> Result<(), GameError> {
     if super::is_running(&game.executable) {
-        return Err("Game is already running".to_string());
+        return Err(GameError::GameRunning);
+    }
+
+    // Without the entitlements, macOS strips the DYLD variables and the game starts unmodded
+    let status = super::entitlements::check(&game.executable)
+        .map_err(|e| GameError::Failed(format!("Could not check entitlements: {e}")))?;
+    if !status.all_granted() {
+        let names = status.missing_short().into_iter().map(String::from).collect();
+        return Err(GameError::MissingEntitlements(names));
     }
 
     let lib_dir = mod_library
         .parent()
         .ok_or_else(|| "Could not determine mod library directory".to_string())?;
+    check_architecture(&game.executable, mod_library)?;
+    remove_quarantine(mod_library);
 
     log_info!("Launching {} with mod {}", game.executable.display(), mod_library.display());
+    if !args.is_empty() {
+        log_info!("Game arguments: {}", args.join(" "));
+    }
+    let library_path = dyld_library_path(library_paths, lib_dir);
+    if !library_paths.is_empty() {
+        log_info!("Library search path: {}", library_path.to_string_lossy());
+    }
 
-    Command::new(&game.executable)
-        .current_dir(&game.install_dir)
-        .env("DYLD_INSERT_LIBRARIES", mod_library)
-        .env("DYLD_LIBRARY_PATH", lib_dir)
-        .spawn()
-        .map_err(|e| {
-            log_error!("Failed to spawn game process: {e}");
-            "Failed to launch game (see log for details)".to_string()
-        })?;
-
-    log_info!("Game process spawned");
-    Ok(())
+    let env = [("DYLD_INSERT_LIBRARIES", mod_library.as_os_str()), ("DYLD_LIBRARY_PATH", library_path.as_os_str())];
+    match launch_mode(std::env::var(LAUNCH_MODE_ENV).ok().as_deref()) {
+        LaunchMode::Direct => {
+            let mut cmd = game_command(game, args);
+            cmd.envs(env);
+            spawn_game(&mut cmd, on_output)
+        }
+        LaunchMode::Bundle => {
+            log_info!("Launching via the app bundle ({LAUNCH_MODE_ENV}=bundle)");
+            launch_bundle(game, &env, args, on_output)
+        }
+    }
 }
 
 /// Launch the game on Windows with automatic mod DLL deployment.
@@ -45,10 +592,19 @@ pub fn launch(game: &GameInfo, mod_library: &Path) -> Result<(), String> {
 /// If `version.dll` is missing or outdated in the game directory, the bundled DLL is copied before spawning
 /// the game process.
 /// Windows loads `version.dll` from the application directory automatically (DLL proxy injection).
+/// `args` are passed through to the game executable unchanged, stdout/stderr are captured via [`redirect_output`].
+/// `library_paths` only apply to DYLD injection and are ignored. With `on_output`, the game output is also forwarded
+/// to it line by line, see [`spawn_game`].
 #[cfg(target_os = "windows")]
-pub fn launch(game: &GameInfo, mod_library: &Path) -> Result<(), String> {
+pub fn launch(
+    game: &GameInfo,
+    mod_library: &Path,
+    args: &[String],
+    _library_paths: &[PathBuf],
+    on_output: Option<OutputSink>,
+) -> Result<(), GameError> {
     if super::is_running(&game.executable) {
-        return Err("Game is already running".to_string());
+        return Err(GameError::GameRunning);
     }
 
     // Auto-deploy: copy the bundled DLL if missing or outdated
@@ -61,23 +617,147 @@ pub fn launch(game: &GameInfo, mod_library: &Path) -> Result<(), String> {
     }
 
     log_info!("Launching {}", game.executable.display());
+    if !args.is_empty() {
+        log_info!("Game arguments: {}", args.join(" "));
+    }
 
-    Command::new(&game.executable)
-        .current_dir(&game.install_dir)
-        .spawn()
-        .map_err(|e| {
-            log_error!("Failed to spawn game process: {e}");
-            "Failed to launch game (see log for details)".to_string()
-        })?;
+    spawn_game(&mut game_command(game, args), on_output)
+}
 
-    log_info!("Game process spawned");
-    Ok(())
+/// Refuse to launch on Linux: the game runs under Proton, where neither DYLD nor `version.dll` injection applies.
+#[cfg(target_os = "linux")]
+pub fn launch(
+    _game: &GameInfo,
+    _mod_library: &Path,
+    _args: &[String],
+    _: &[PathBuf],
+    _: Option<OutputSink>,
+) -> Result<(), GameError> {
+    Err(GameError::Failed("Mod injection is not supported under Proton — start the game via Steam".to_string()))
+}
+
+/// Stub — game launching is not yet supported on this platform.
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn launch(
+    _game: &GameInfo,
+    _mod_library: &Path,
+    _args: &[String],
+    _: &[PathBuf],
+    _: Option<OutputSink>,
+) -> Result<(), GameError> {
+    Err(GameError::Failed("Game launching is not yet supported on this platform".to_string()))
+}
+
+/// Launch the game without injecting the mod, e.g. to check whether a bug is caused by the mod.
+///
+/// No DYLD variables are set and no entitlement precondition applies. On Windows the game loads a deployed
+/// `version.dll` on its own, so a vanilla launch is refused until the mod is removed.
+/// Returns an error if the game is already running or the process fails to spawn.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+pub fn launch_vanilla(game: &GameInfo, args: &[String]) -> Result<(), GameError> {
+    if super::is_running(&game.executable) {
+        return Err(GameError::GameRunning);
+    }
+
+    #[cfg(target_os = "windows")]
+    if game.install_dir.join("version.dll").exists() {
+        return Err(GameError::Failed("The mod is deployed — remove it first to launch without it".to_string()));
+    }
+
+    log_info!("Launching {} vanilla, without mod injection", game.executable.display());
+    if !args.is_empty() {
+        log_info!("Game arguments: {}", args.join(" "));
+    }
+
+    spawn_game(&mut game_command(game, args), None)
 }
 
 /// Stub — game launching is not yet supported on this platform.
 #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-pub fn launch(_game: &GameInfo, _mod_library: &Path) -> Result<(), String> {
-    Err("Game launching is not yet supported on this platform".to_string())
+pub fn launch_vanilla(_game: &GameInfo, _args: &[String]) -> Result<(), GameError> {
+    Err(GameError::Failed("Game launching is not yet supported on this platform".to_string()))
+}
+
+/// Terminate the running game process.
+///
+/// On macOS and Linux, uses `pkill -f` with the same pattern as the `pgrep -f` check in [`super::is_running`].
+/// On Windows, uses `taskkill /F /IM` with the executable's image name.
+/// Returns an error if the kill command cannot be run or reports failure.
+pub fn stop(game: &GameInfo) -> Result<(), GameError> {
+    let name = game.executable.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    if name.is_empty() {
+        return Err(GameError::Failed("Could not determine the game executable name".to_string()));
+    }
+
+    log_info!("Stopping {}", game.executable.display());
+
+    #[cfg(target_os = "windows")]
+    let output = super::silent_command("taskkill").args(["/F", "/IM", name]).output();
+    #[cfg(not(target_os = "windows"))]
+    let output = Command::new("pkill").arg("-f").arg(super::game_process_pattern(&game.executable)).output();
+
+    match output {
+        Ok(out) if out.status.success() => {
+            log_info!("Game process terminated");
+            Ok(())
+        }
+        Ok(out) => {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            log_error!("Failed to terminate game process ({}): {stderr}", out.status);
+            Err(GameError::Failed("Failed to stop game (see log for details)".to_string()))
+        }
+        Err(e) => {
+            log_error!("Could not run kill command: {e}");
+            Err(GameError::Failed("Failed to stop game (see log for details)".to_string()))
+        }
+    }
+}
+
+/// Stop the game if it is running, wait for the process to exit, and launch it again with the mod injected.
+///
+/// Arguments are the same as for [`launch`]. Returns an error if the game cannot be stopped, the process is still
+/// running after [`RELAUNCH_EXIT_TIMEOUT`], or the launch fails.
+pub fn relaunch(
+    game: &GameInfo,
+    mod_library: &Path,
+    args: &[String],
+    library_paths: &[PathBuf],
+    on_output: Option<OutputSink>,
+) -> Result<(), GameError> {
+    if super::is_running(&game.executable) {
+        log_info!("Relaunch: stopping the running game");
+        stop(game)?;
+
+        log_debug!("Relaunch: waiting for the game process to exit");
+        let exited = wait_until(RELAUNCH_EXIT_TIMEOUT, RELAUNCH_POLL_INTERVAL, || !super::is_running(&game.executable));
+        if !exited {
+            log_error!("Relaunch: game process still running after {}s", RELAUNCH_EXIT_TIMEOUT.as_secs());
+            return Err(GameError::Failed("The game did not exit in time, relaunch aborted".to_string()));
+        }
+        log_info!("Relaunch: game process exited");
+    } else {
+        log_debug!("Relaunch: game is not running, launching directly");
+    }
+
+    log_info!("Relaunch: starting the game");
+    launch(game, mod_library, args, library_paths, on_output)
+}
+
+/// Poll `condition` every `interval` until it returns `true` or `timeout` has elapsed.
+///
+/// Returns whether the condition was met. The condition is checked at least once, and once more at the deadline.
+fn wait_until(timeout: Duration, interval: Duration, mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if condition() {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        thread::sleep(interval.min(deadline - now));
+    }
 }
 
 /// Open the Scopely launcher so the user can install a game update.
@@ -110,10 +790,237 @@ pub fn open_updater() -> Result<(), String> {
             })?;
     }
 
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    {
+    if cfg!(not(any(target_os = "macos", target_os = "windows"))) {
         return Err("Opening the launcher is not supported on this platform".to_string());
     }
 
     Ok(())
 }
+
+// ---- Tests ----
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn game_info() -> GameInfo {
+        GameInfo {
+            install_dir: PathBuf::from("/Games/STFC"),
+            executable: PathBuf::from("/Games/STFC/prime"),
+            installed_version: None,
+            bundle_version: None,
+        }
+    }
+
+    #[test]
+    fn game_command_without_args() {
+        let cmd = game_command(&game_info(), &[]);
+        assert_eq!(cmd.get_program(), OsStr::new("/Games/STFC/prime"));
+        assert_eq!(cmd.get_current_dir(), Some(Path::new("/Games/STFC")));
+        assert_eq!(cmd.get_args().count(), 0);
+    }
+
+    #[test]
+    fn game_command_passes_args_through() {
+        let args = vec!["-server".to_string(), "eu 1".to_string()];
+        let cmd = game_command(&game_info(), &args);
+        let passed: Vec<_> = cmd.get_args().collect();
+        assert_eq!(passed, [OsStr::new("-server"), OsStr::new("eu 1")]);
+    }
+
+    /// Build a thin little-endian 64-bit Mach-O header of the given file type.
+    fn macho_header(file_type: u32) -> Vec<u8> {
+        let mut header = vec![0xcf, 0xfa, 0xed, 0xfe, 0x0c, 0, 0, 0x01, 0, 0, 0, 0];
+        header.extend(file_type.to_le_bytes());
+        header.extend([0u8; 16]);
+        header
+    }
+
+    #[test]
+    fn macho_dylib_detection() {
+        let check = |bytes: Vec<u8>| is_macho_dylib(&mut std::io::Cursor::new(bytes)).unwrap();
+        assert!(check(macho_header(MH_DYLIB)));
+        // MH_EXECUTE
+        assert!(!check(macho_header(2)));
+        assert!(!check(b"#!/bin/sh\necho not a library\n".to_vec()));
+        assert!(!check(vec![0xcf, 0xfa]));
+    }
+
+    #[test]
+    fn macho_dylib_detection_universal() {
+        // Fat header with one slice at offset 64
+        let mut fat = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 1, 0x01, 0, 0, 0x0c, 0, 0, 0, 0, 0, 0, 0, 64];
+        fat.resize(64, 0);
+        fat.extend(macho_header(MH_DYLIB));
+        assert!(is_macho_dylib(&mut std::io::Cursor::new(fat)).unwrap());
+    }
+
+    #[test]
+    fn validate_dylib_reports_missing_file() {
+        let report = validate_dylib(Path::new("/nonexistent/libmod.dylib"), None);
+        assert!(!report.exists && !report.is_valid());
+        assert_eq!(report.messages.len(), 1);
+    }
+
+    #[test]
+    fn validate_dylib_rejects_non_dylib() {
+        let dir = std::env::temp_dir().join("daystrom_test_validate_dylib");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("notes.dylib");
+        fs::write(&file, "not a library").unwrap();
+
+        let report = validate_dylib(&file, None);
+        assert!(report.exists);
+        assert!(!report.is_dylib && !report.is_valid());
+        assert_eq!(report.messages, ["The file is not a Mach-O dynamic library"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn launch_mode_parsing() {
+        assert_eq!(launch_mode(None), LaunchMode::Direct);
+        assert_eq!(launch_mode(Some("direct")), LaunchMode::Direct);
+        assert_eq!(launch_mode(Some(" Bundle ")), LaunchMode::Bundle);
+        assert_eq!(launch_mode(Some("open")), LaunchMode::Direct);
+    }
+
+    #[test]
+    fn bundle_command_passes_env_output_and_args() {
+        let env = [("DYLD_INSERT_LIBRARIES", OsStr::new("/mods/libmod.dylib"))];
+        let args = vec!["-server".to_string()];
+        let cmd = bundle_command(Path::new("/Games/STFC.app"), &env, &args, Some(Path::new("/logs/game.log")));
+        assert_eq!(cmd.get_program(), OsStr::new("open"));
+        let passed: Vec<_> = cmd.get_args().collect();
+        assert_eq!(
+            passed,
+            [
+                "-n", "-a", "/Games/STFC.app", "--env", "DYLD_INSERT_LIBRARIES=/mods/libmod.dylib", "--stdout",
+                "/logs/game.log", "--stderr", "/logs/game.log", "--args", "-server",
+            ]
+            .map(OsStr::new)
+        );
+    }
+
+    #[test]
+    fn bundle_command_without_output_and_args() {
+        let cmd = bundle_command(Path::new("/Games/STFC.app"), &[], &[], None);
+        let passed: Vec<_> = cmd.get_args().collect();
+        assert_eq!(passed, ["-n", "-a", "/Games/STFC.app"].map(OsStr::new));
+    }
+
+    #[test]
+    fn tail_lines_passes_each_line() {
+        let lines = std::sync::Mutex::new(Vec::new());
+        let sink = |line: GameOutputLine| lines.lock().unwrap().push(line.line);
+        let reader = std::io::Cursor::new(b"first\r\nsecond\n\xffthird".to_vec());
+        tail_lines(reader, || true, &sink);
+
+        assert_eq!(lines.into_inner().unwrap(), ["first", "second", "\u{fffd}third"]);
+    }
+
+    #[test]
+    fn tail_lines_follows_a_growing_file() {
+        let dir = std::env::temp_dir().join("daystrom_test_tail_lines");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(GAME_OUTPUT_FILE);
+        let mut writer = fs::File::create(&path).unwrap();
+        writer.write_all(b"started\nhalf").unwrap();
+
+        let lines = std::sync::Mutex::new(Vec::new());
+        let sink = |line: GameOutputLine| lines.lock().unwrap().push(line.line);
+        let mut polls = 0;
+        let exited = || {
+            polls += 1;
+            // The game writes the rest of the line and a last one, then exits
+            if polls == 1 {
+                writer.write_all(b" done\nlast\n").unwrap();
+            }
+            polls > 1
+        };
+        tail_lines(BufReader::new(fs::File::open(&path).unwrap()), exited, &sink);
+
+        assert_eq!(lines.into_inner().unwrap(), ["started", "half done", "last"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn dyld_library_path_prepends_extra_dirs() {
+        let extra = [PathBuf::from("/opt/deps"), PathBuf::from("/Users/jane/lib")];
+        let value = dyld_library_path(&extra, Path::new("/Apps/Daystrom/mods"));
+        assert_eq!(value, OsStr::new("/opt/deps:/Users/jane/lib:/Apps/Daystrom/mods"));
+    }
+
+    #[test]
+    fn dyld_library_path_without_extra_dirs() {
+        assert_eq!(dyld_library_path(&[], Path::new("/Apps/Daystrom/mods")), OsStr::new("/Apps/Daystrom/mods"));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn quarantine_attribute_is_detected_and_removed() {
+        let dir = std::env::temp_dir().join("daystrom_test_quarantine");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let lib = dir.join("libmod.dylib");
+        fs::write(&lib, b"dylib").unwrap();
+
+        assert!(!has_xattr(&lib, QUARANTINE_ATTR));
+        let status = Command::new("xattr").args(["-w", QUARANTINE_ATTR, "0083;00000000;Safari;"]).arg(&lib).status();
+        assert!(status.unwrap().success());
+        assert!(has_xattr(&lib, QUARANTINE_ATTR));
+
+        remove_quarantine(&lib);
+        assert!(!has_xattr(&lib, QUARANTINE_ATTR));
+        // Without the attribute, removal is a no-op
+        remove_quarantine(&lib);
+        remove_quarantine(&dir.join("missing.dylib"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn wait_until_returns_once_condition_holds() {
+        let mut polls = 0;
+        let met = wait_until(Duration::from_secs(5), Duration::from_millis(1), || {
+            polls += 1;
+            polls == 3
+        });
+        assert!(met);
+        assert_eq!(polls, 3);
+    }
+
+    #[test]
+    fn wait_until_times_out() {
+        let start = Instant::now();
+        assert!(!wait_until(Duration::from_millis(20), Duration::from_millis(5), || false));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn parse_archs_universal() {
+        assert_eq!(parse_archs("x86_64 arm64\n"), ["x86_64", "arm64"]);
+    }
+
+    #[test]
+    fn parse_archs_empty() {
+        assert!(parse_archs("").is_empty());
+    }
+
+    #[test]
+    fn runtime_arch_prefers_host() {
+        assert_eq!(runtime_arch(&["x86_64", "arm64"], "arm64"), Some("arm64"));
+    }
+
+    #[test]
+    fn runtime_arch_falls_back_to_first_slice() {
+        assert_eq!(runtime_arch(&["x86_64"], "arm64"), Some("x86_64"));
+        assert_eq!(runtime_arch(&[], "arm64"), None);
+    }
+}
@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+
+use crate::use_log;
+
+use_log!("GameDetect");
+
+/// Name of the Windows game executable that Proton runs.
+pub(super) const EXECUTABLE_NAME: &str = "prime.exe";
+
+/// Candidate Steam root directories, relative to the user's home directory: native install, legacy symlink,
+/// and Flatpak.
+const STEAM_ROOTS: [&str; 3] = [
+    ".local/share/Steam",
+    ".steam/steam",
+    ".var/app/com.valvesoftware.Steam/data/Steam",
+];
+
+/// Path to the Scopely launcher directory inside a Proton prefix, relative to its `drive_c`.
+const PREFIX_LAUNCHER_DIR: &str = "users/steamuser/AppData/Local/Star Trek Fleet Command";
+
+/// Name of the Scopely launcher settings file inside [`PREFIX_LAUNCHER_DIR`].
+const LAUNCHER_SETTINGS_FILE: &str = "launcher_settings.ini";
+
+/// Default game directory, relative to the launcher directory.
+const DEFAULT_GAME_DIR: &str = "default/game";
+
+/// Locate all STFC installations run through Proton: Steam libraries first, then Scopely launcher installs inside
+/// any Proton prefix (`steamapps/compatdata/*/pfx`), e.g. when the launcher was added as a non-Steam game.
+///
+/// Returns install directory and executable path pairs, with the executable being the Windows `.exe` Proton runs.
+pub fn detect_all() -> Vec<(PathBuf, PathBuf)> {
+    let Some(home) = crate::logging::home_dir() else { return Vec::new() };
+
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for root in STEAM_ROOTS.iter().map(|rel| home.join(rel)) {
+        // `.steam/steam` is usually a symlink to `.local/share/Steam`
+        let root = root.canonicalize().unwrap_or(root);
+        if root.is_dir() && !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+
+    let mut found = Vec::new();
+    for root in &roots {
+        if let Some(install) = super::steam::detect(root, EXECUTABLE_NAME) {
+            log_debug!("STFC detected via Steam library at {}", root.display());
+            found.push(install);
+        }
+    }
+    for root in &roots {
+        for prefix in proton_prefixes(root) {
+            if let Some(install) = detect_in_prefix(&prefix) {
+                log_debug!("STFC detected in Proton prefix {}", prefix.display());
+                found.push(install);
+            }
+        }
+    }
+    found
+}
+
+/// List the Proton prefixes (`steamapps/compatdata/<appid>/pfx`) below a Steam root.
+fn proton_prefixes(steam_root: &Path) -> Vec<PathBuf> {
+    let compatdata = steam_root.join("steamapps/compatdata");
+    let Ok(entries) = std::fs::read_dir(&compatdata) else { return Vec::new() };
+    entries
+        .flatten()
+        .map(|entry| entry.path().join("pfx"))
+        .filter(|pfx| pfx.join("drive_c").is_dir())
+        .collect()
+}
+
+/// Locate a Scopely launcher install inside a Proton prefix.
+///
+/// Reads `GAME_PATH` from the launcher settings in the prefix and maps it onto the prefix's drive, falling back to
+/// the launcher's default game directory.
+fn detect_in_prefix(prefix: &Path) -> Option<(PathBuf, PathBuf)> {
+    let launcher_dir = prefix.join("drive_c").join(PREFIX_LAUNCHER_DIR);
+    let settings = launcher_dir.join(LAUNCHER_SETTINGS_FILE);
+
+    if let Ok(content) = std::fs::read_to_string(&settings) {
+        let mapped = super::read_game_path(&content).and_then(|raw| {
+            log_debug!("Raw GAME_PATH value in prefix: {raw}");
+            prefix_path(prefix, raw)
+        });
+        if let Some(install) = mapped.and_then(|dir| super::validate_install_dir(&dir, EXECUTABLE_NAME)) {
+            return Some(install);
+        }
+    }
+
+    super::validate_install_dir(&launcher_dir.join(DEFAULT_GAME_DIR), EXECUTABLE_NAME)
+}
+
+/// Map a Windows path from inside a Proton prefix (e.g. `C:/Games/STFC/`) onto the host filesystem.
+///
+/// `C:` lives in the prefix's `drive_c`, `Z:` is Wine's mapping of the host root. Other drive letters are
+/// resolved through the prefix's `dosdevices` symlinks. Returns `None` if the value is not an absolute drive path.
+fn prefix_path(prefix: &Path, windows_path: &str) -> Option<PathBuf> {
+    let windows_path = windows_path.trim().trim_matches('"');
+    let (drive, rest) = windows_path.split_once(':')?;
+    let mut chars = drive.chars();
+    let (Some(letter), None) = (chars.next(), chars.next()) else { return None };
+    if !letter.is_ascii_alphabetic() {
+        return None;
+    }
+
+    let base = match letter.to_ascii_lowercase() {
+        'c' => prefix.join("drive_c"),
+        'z' => PathBuf::from("/"),
+        other => prefix.join("dosdevices").join(format!("{other}:")),
+    };
+    Some(rest.split(['/', '\\']).filter(|part| !part.is_empty()).fold(base, |path, part| path.join(part)))
+}
+
+/// Build the `pgrep -f`/`pkill -f` pattern for the game process running under Proton/Wine.
+///
+/// Wine shows the executable as a Windows path (`Z:\home\...\prime.exe` or `C:\...\prime.exe`) or as the host path
+/// Proton was started with, so only the file name after a path separator can be matched reliably.
+pub(super) fn process_pattern(executable: &Path) -> String {
+    let name = executable.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    format!("[\\\\/]{}( |$)", super::escape_ere(&name))
+}
+
+// ---- Tests ----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_path_drive_c() {
+        let prefix = Path::new("/steam/steamapps/compatdata/123/pfx");
+        assert_eq!(
+            prefix_path(prefix, "C:/Games/STFC/default/game/"),
+            Some(prefix.join("drive_c/Games/STFC/default/game"))
+        );
+    }
+
+    #[test]
+    fn prefix_path_backslashes_and_quotes() {
+        let prefix = Path::new("/pfx");
+        assert_eq!(
+            prefix_path(prefix, "\"C:\\Program Files\\STFC\\\""),
+            Some(PathBuf::from("/pfx/drive_c/Program Files/STFC"))
+        );
+    }
+
+    #[test]
+    fn prefix_path_host_and_other_drives() {
+        let prefix = Path::new("/pfx");
+        assert_eq!(prefix_path(prefix, "Z:/home/me/stfc"), Some(PathBuf::from("/home/me/stfc")));
+        assert_eq!(prefix_path(prefix, "D:/stfc"), Some(PathBuf::from("/pfx/dosdevices/d:/stfc")));
+    }
+
+    #[test]
+    fn prefix_path_rejects_relative() {
+        assert_eq!(prefix_path(Path::new("/pfx"), "Games/STFC"), None);
+        assert_eq!(prefix_path(Path::new("/pfx"), "CD:/Games"), None);
+    }
+
+    #[test]
+    fn detect_in_prefix_reads_launcher_settings() {
+        let prefix = std::env::temp_dir().join("daystrom_test_proton_prefix");
+        let _ = std::fs::remove_dir_all(&prefix);
+        let launcher_dir = prefix.join("drive_c").join(PREFIX_LAUNCHER_DIR);
+        let game_dir = prefix.join("drive_c/Games/STFC/default/game");
+        std::fs::create_dir_all(&launcher_dir).unwrap();
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::write(game_dir.join(EXECUTABLE_NAME), "").unwrap();
+        std::fs::write(
+            launcher_dir.join(LAUNCHER_SETTINGS_FILE),
+            "[General]\n152033..GAME_PATH=C:/Games/STFC/default/game/\n",
+        )
+        .unwrap();
+
+        assert_eq!(detect_in_prefix(&prefix), Some((game_dir.clone(), game_dir.join(EXECUTABLE_NAME))));
+
+        let _ = std::fs::remove_dir_all(&prefix);
+    }
+
+    #[test]
+    fn detect_in_prefix_falls_back_to_default_dir() {
+        let prefix = std::env::temp_dir().join("daystrom_test_proton_default");
+        let _ = std::fs::remove_dir_all(&prefix);
+        let game_dir = prefix.join("drive_c").join(PREFIX_LAUNCHER_DIR).join(DEFAULT_GAME_DIR);
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::write(game_dir.join(EXECUTABLE_NAME), "").unwrap();
+
+        assert_eq!(detect_in_prefix(&prefix), Some((game_dir.clone(), game_dir.join(EXECUTABLE_NAME))));
+
+        let _ = std::fs::remove_dir_all(&prefix);
+    }
+
+    #[test]
+    fn process_pattern_matches_file_name() {
+        let exe = Path::new("/steam/steamapps/common/Star Trek Fleet Command/prime.exe");
+        assert_eq!(process_pattern(exe), "[\\\\/]prime\\.exe( |$)");
+    }
+}
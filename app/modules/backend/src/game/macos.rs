@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::use_log;
 
@@ -9,34 +9,58 @@ const LAUNCHER_SETTINGS_PATH: &str =
     "Library/Preferences/Star Trek Fleet Command/launcher_settings.ini";
 
 /// Path to the game executable, relative to the install directory.
-const EXECUTABLE_REL: &str =
+pub(super) const EXECUTABLE_REL: &str =
     "Star Trek Fleet Command.app/Contents/MacOS/Star Trek Fleet Command";
 
-/// Locate the STFC installation by reading the Scopely launcher settings INI.
+/// Path to the Steam root directory, relative to the user's home directory.
+const STEAM_ROOT: &str = "Library/Application Support/Steam";
+
+/// Locate all STFC installations: the Xsolla launcher's install first, then Steam.
+///
+/// Returns install directory and executable path pairs in that order; empty if neither source finds the game.
+pub fn detect_all() -> Vec<(PathBuf, PathBuf)> {
+    let mut found = Vec::new();
+    if let Some(install) = detect_xsolla() {
+        log_debug!("STFC detected via Xsolla launcher settings");
+        found.push(install);
+    }
+    if let Some(home) = crate::logging::home_dir() {
+        if let Some(install) = super::steam::detect(&home.join(STEAM_ROOT), EXECUTABLE_REL) {
+            log_debug!("STFC detected via Steam library");
+            found.push(install);
+        }
+    }
+    found
+}
+
+/// Full path to the Scopely launcher settings INI, or `None` if the home directory is unknown.
+pub fn launcher_settings_path() -> Option<PathBuf> {
+    Some(crate::logging::home_dir()?.join(LAUNCHER_SETTINGS_PATH))
+}
+
+/// Locate the STFC installation by reading the Scopely launcher settings INI at its default location.
+///
+/// See [`detect_xsolla_at`]; returns `None` if the home directory is unknown.
+fn detect_xsolla() -> Option<(PathBuf, PathBuf)> {
+    detect_xsolla_at(&launcher_settings_path()?)
+}
+
+/// Locate the STFC installation by reading the Scopely launcher settings INI at `ini_path`.
 ///
 /// Returns the install directory and executable path as a tuple, or `None`
 /// (with debug/warn logging) if the settings file is missing, the game path
 /// key is absent, or the executable does not exist on disk.
-pub fn detect() -> Option<(PathBuf, PathBuf)> {
-    let home = dirs::home_dir()?;
-    let ini_path = home.join(LAUNCHER_SETTINGS_PATH);
+fn detect_xsolla_at(ini_path: &Path) -> Option<(PathBuf, PathBuf)> {
     log_debug!("Looking for launcher settings at {}", ini_path.display());
 
-    let content = std::fs::read_to_string(&ini_path)
+    let content = std::fs::read_to_string(ini_path)
         .map_err(|e| log_debug!("Could not read launcher settings: {e}"))
         .ok()?;
 
     let raw_path = super::read_game_path(&content)?;
     log_debug!("Raw GAME_PATH value: {raw_path}");
 
-    // Scopely launcher quirk: path may start with "//" instead of "/"
-    let normalised = if raw_path.starts_with("//") {
-        raw_path.strip_prefix('/').unwrap_or(raw_path)
-    } else {
-        raw_path
-    };
-
-    let install_dir = PathBuf::from(normalised);
+    let install_dir = PathBuf::from(super::normalise_game_path(raw_path));
     let executable = install_dir.join(EXECUTABLE_REL);
 
     if !executable.exists() {
@@ -49,3 +73,33 @@ pub fn detect() -> Option<(PathBuf, PathBuf)> {
 
     Some((install_dir, executable))
 }
+
+// ---- Tests ----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_xsolla_finds_executable_in_fake_install() {
+        let dir = std::env::temp_dir().join("daystrom_test_detect_xsolla");
+        let _ = std::fs::remove_dir_all(&dir);
+        let install_dir = dir.join("Games/Star Trek Fleet Command");
+        let executable = install_dir.join(EXECUTABLE_REL);
+        std::fs::create_dir_all(executable.parent().unwrap()).unwrap();
+        std::fs::write(&executable, "").unwrap();
+
+        let ini_path = dir.join("launcher_settings.ini");
+        assert_eq!(detect_xsolla_at(&ini_path), None);
+
+        // The launcher sometimes writes a doubled leading slash
+        let ini = format!("[General]\n152033..GAME_PATH=/{}\nREGION_INFO=@ByteArray(\\0)\n", install_dir.display());
+        std::fs::write(&ini_path, ini).unwrap();
+        assert_eq!(detect_xsolla_at(&ini_path), Some((install_dir, executable.clone())));
+
+        std::fs::remove_file(&executable).unwrap();
+        assert_eq!(detect_xsolla_at(&ini_path), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
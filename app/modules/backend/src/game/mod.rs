@@ -1,17 +1,22 @@
-#[cfg(target_os = "windows")]
 use std::io;
 #[cfg(target_os = "windows")]
 use std::io::Read;
+use std::fmt;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
+use std::sync::Mutex;
 #[cfg(target_os = "windows")]
 use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 use sha2::{Digest, Sha256};
+use serde::Serialize;
 use tauri::Manager;
+use ts_rs::TS;
 
 use crate::use_log;
 
@@ -22,10 +27,6 @@ const CREATE_NO_WINDOW: u32 = 0x08000000;
 #[cfg(target_os = "windows")]
 static LAUNCHER_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
 
-/// Cached full path to the STFC game executable (Windows only).
-#[cfg(target_os = "windows")]
-static GAME_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
-
 /// Create a `Command` that won't spawn a visible console window on Windows.
 ///
 /// On non-Windows platforms this is equivalent to `Command::new(program)`.
@@ -37,6 +38,52 @@ pub(crate) fn silent_command(program: &str) -> Command {
     cmd
 }
 
+/// Interval at which [`output_with_timeout`] checks whether the child has exited.
+const TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Run `cmd` like [`Command::output`], but kill it if it has not exited after `timeout`.
+///
+/// stdout and stderr are read on background threads, so a child filling its pipes cannot stall the deadline.
+/// Returns an error of kind [`io::ErrorKind::TimedOut`] if the child was killed.
+pub(crate) fn output_with_timeout(cmd: &mut Command, timeout: Duration) -> io::Result<Output> {
+    let mut child = cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("no exit after {}ms", timeout.as_millis()),
+            ));
+        }
+        thread::sleep(TIMEOUT_POLL_INTERVAL);
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Read a child's pipe to the end on a background thread.
+fn read_pipe(pipe: Option<impl io::Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
 /// INI key (with `=` suffix) that holds the game installation directory.
 const GAME_PATH_KEY: &str = "152033..GAME_PATH=";
 
@@ -52,21 +99,85 @@ fn read_game_path(content: &str) -> Option<&str> {
     None
 }
 
+/// Replace every `/` and `\\` in `path` with the platform's separator.
+///
+/// The Scopely launcher writes `GAME_PATH` with whichever separator it likes; a path that already uses the platform
+/// separator is returned unchanged.
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+fn normalise_separators(path: &str) -> String {
+    path.replace(['/', '\\'], std::path::MAIN_SEPARATOR_STR)
+}
+
+/// Clean up a raw `GAME_PATH` value from the launcher INI.
+///
+/// Trims surrounding whitespace and quotes, turns backslashes into `/`, and collapses any run of leading slashes to
+/// one — the Scopely launcher on macOS sometimes writes `//Users/...` or even `///Users/...`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn normalise_game_path(raw: &str) -> String {
+    let trimmed = normalise_separators(raw.trim().trim_matches(|c| c == '"' || c == '\'').trim());
+    match trimmed.strip_prefix('/') {
+        Some(rest) => format!("/{}", rest.trim_start_matches('/')),
+        None => trimmed.to_string(),
+    }
+}
+
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(target_os = "windows")]
 mod windows;
+#[cfg(target_os = "linux")]
+mod linux;
 #[cfg(target_os = "macos")]
 pub mod entitlements;
 
 #[cfg(not(target_os = "macos"))]
 pub mod entitlements {
+    use std::collections::BTreeMap;
     use std::path::Path;
 
+    use serde::Serialize;
+    use ts_rs::TS;
+
+    // Only defined so `GameStatus` has the same shape on every platform; the stub never constructs it.
+    /// State of a single entitlement in the game executable's code signature.
+    #[allow(dead_code)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+    #[serde(rename_all = "lowercase")]
+    #[ts(export)]
+    pub enum EntitlementState {
+        /// The key is present with `<true/>`.
+        Granted,
+        /// The key is present with any other value (usually `<false/>`).
+        Denied,
+        /// The key is not present in the signature.
+        Absent,
+    }
+
+    // Only defined so the generated TypeScript bindings are the same on every platform; never reported here.
+    /// Step of entitlement patching reported via `patch-progress` events (macOS only).
+    #[allow(dead_code)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, TS)]
+    #[serde(rename_all = "snake_case")]
+    #[ts(export)]
+    pub enum PatchStage {
+        /// Saving the current entitlements for a later restore.
+        BackingUp,
+        /// Removing updater leftovers from the app bundle.
+        CleaningBundle,
+        /// Generating the entitlements plist.
+        WritingPlist,
+        /// Re-signing the executable.
+        RunningCodesign,
+        /// Checking the new signature.
+        Verifying,
+    }
+
     /// Result of checking the game executable's code-signing entitlements.
     pub struct EntitlementStatus {
         /// Entitlement keys that are absent or not `true`.
         pub missing: Vec<&'static str>,
+        /// State of each required entitlement, keyed by entitlement name.
+        pub states: BTreeMap<&'static str, EntitlementState>,
     }
 
     impl EntitlementStatus {
@@ -74,74 +185,390 @@ pub mod entitlements {
         pub fn all_granted(&self) -> bool {
             self.missing.is_empty()
         }
+
+        /// Missing entitlement keys without the `com.apple.security.` prefix, for messages shown to the user.
+        pub fn missing_short(&self) -> Vec<&'static str> {
+            self.missing.iter().map(|key| key.strip_prefix("com.apple.security.").unwrap_or(key)).collect()
+        }
+
+        /// Granted entitlement keys without the `com.apple.security.` prefix, for messages shown to the user.
+        pub fn granted_short(&self) -> Vec<&'static str> {
+            self.states
+                .iter()
+                .filter(|(_, state)| **state == EntitlementState::Granted)
+                .map(|(key, _)| key.strip_prefix("com.apple.security.").unwrap_or(key))
+                .collect()
+        }
     }
 
     /// Stub — entitlements are a macOS concept; always returns empty on other platforms.
-    pub fn check(_executable: &Path) -> EntitlementStatus {
-        EntitlementStatus { missing: vec![] }
+    pub fn check(_executable: &Path) -> Result<EntitlementStatus, String> {
+        Ok(EntitlementStatus { missing: vec![], states: BTreeMap::new() })
+    }
+
+    // Only defined so the generated TypeScript bindings are the same on every platform; never constructed here.
+    /// Changes entitlement patching would make to the code signature (macOS only).
+    #[allow(dead_code)]
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, TS)]
+    #[ts(export)]
+    pub struct PatchPlan {
+        /// Entitlement keys not present in the current signature.
+        pub added: Vec<String>,
+        /// Entitlement keys present but not `true`, which would be switched on.
+        pub enabled: Vec<String>,
+        /// Entitlement keys that are already granted.
+        pub unchanged: Vec<String>,
+        /// The entitlements plist that would be passed to `codesign`.
+        pub plist: String,
+    }
+
+    /// Availability of the tools entitlement patching depends on (macOS only).
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, TS)]
+    #[ts(export)]
+    pub struct Prerequisites {
+        /// Whether `codesign` can be run at all.
+        pub codesign_available: bool,
+        /// Active developer directory reported by `xcode-select -p`, if the command line tools are set up.
+        pub developer_dir: Option<String>,
+        /// Version of the installed Xcode command line tools package, if found.
+        pub tools_version: Option<String>,
+    }
+
+    /// Stub — `codesign` and the Xcode tools only exist on macOS, so nothing is available here.
+    pub fn prerequisites() -> Prerequisites {
+        Prerequisites { codesign_available: false, developer_dir: None, tools_version: None }
+    }
+
+    // Only defined so the generated TypeScript bindings are the same on every platform.
+    /// Whether entitlement patching can rewrite the signature of the game executable (macOS only).
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, TS)]
+    #[ts(export)]
+    pub struct PatchReadiness {
+        /// Whether the executable can be opened for writing by the current user.
+        pub executable_writable: bool,
+        /// Whether the executable lies in a protected location; the game has to be moved.
+        pub protected_location: bool,
+        /// Whether `codesign` can be run at all.
+        pub codesign_available: bool,
+        /// Whether all checks passed.
+        pub ready: bool,
+    }
+
+    /// Stub — there is no code signature to patch on other platforms, so patching is never ready.
+    pub fn patch_readiness(_executable: &Path) -> PatchReadiness {
+        PatchReadiness {
+            executable_writable: false,
+            protected_location: false,
+            codesign_available: false,
+            ready: false,
+        }
+    }
+
+    /// Stub — there is no code signature to patch on other platforms.
+    pub fn plan(_executable: &Path, _extra: &[&str]) -> Result<PatchPlan, String> {
+        Err("Previewing the entitlements patch is only supported on macOS".to_string())
+    }
+
+    /// Stub — there is no code signature to read on other platforms.
+    pub fn read_entitlements_xml(_executable: &Path) -> Result<String, String> {
+        Err("Reading entitlements is only supported on macOS".to_string())
+    }
+
+    /// Stub — there is no code signature to restore on other platforms.
+    pub fn restore(_executable: &Path) -> Result<(), String> {
+        Err("Restoring entitlements is only supported on macOS".to_string())
     }
 }
 pub mod launcher;
+mod steam;
 pub mod version;
 
 use_log!("Game");
 
 /// Location of an STFC installation on the local machine.
+#[derive(Clone)]
 pub struct GameInfo {
-    /// Root directory of the game installation (the `GAME_PATH` from the Scopely launcher settings).
+    /// Root directory of the game installation (the launcher's `GAME_PATH`, or the Steam `steamapps/common` folder).
     pub install_dir: PathBuf,
     /// Full path to the game's main executable binary.
     pub executable: PathBuf,
     /// Installed game version from the `.version` file, if available.
     pub installed_version: Option<u32>,
+    /// Marketing version from the app bundle's `Info.plist` (macOS only), if available.
+    pub bundle_version: Option<String>,
 }
 
-/// Detect whether STFC is installed on this machine.
+/// Error from a game operation, so callers can tell the failure modes apart without parsing the message.
 ///
+/// Serialised as `{ kind, detail }`; [`fmt::Display`] gives the message shown to the user.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, TS)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+#[ts(export)]
+pub enum GameError {
+    /// STFC is not installed or could not be detected.
+    NotInstalled,
+    /// The game is already running.
+    GameRunning,
+    /// Required entitlements are not granted, listed without the `com.apple.security.` prefix (macOS only).
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    MissingEntitlements(Vec<String>),
+    /// The mod library is not bundled with the app.
+    ModNotFound,
+    /// Re-signing the executable failed; details are in the log (macOS only).
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    Codesign(String),
+    /// Any other failure, with a message suitable for display.
+    Failed(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::NotInstalled => f.write_str("STFC not found"),
+            GameError::GameRunning => f.write_str("Game is already running"),
+            GameError::MissingEntitlements(names) => {
+                write!(f, "Missing entitlements: {} — patch them first", names.join(", "))
+            }
+            GameError::ModNotFound => f.write_str("Mod library not found — run build:mod first"),
+            GameError::Codesign(message) | GameError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+impl From<String> for GameError {
+    fn from(message: String) -> Self {
+        GameError::Failed(message)
+    }
+}
+
+/// Environment variable that points detection at a specific install directory.
+const GAME_PATH_ENV: &str = "DAYSTROM_GAME_PATH";
+
+/// Cached result of the last successful [`detect_all`], cleared by [`invalidate_detection`].
+static DETECTED: Mutex<Option<Vec<GameInfo>>> = Mutex::new(None);
+
+/// Install directory chosen by the user via [`select_install`], if any. Not persisted across restarts.
+static SELECTED_INSTALL: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Detect whether STFC is installed on this machine and return the installation to work with.
+///
+/// If several installations exist, the one chosen via [`select_install`] wins, otherwise the first one from
+/// [`detect_all`] (so [`GAME_PATH_ENV`] beats the platform detection).
 /// Returns `None` if the game is not found — errors are logged internally and never block startup.
-/// When found, also reads the installed version from the `.version` file.
 pub fn detect() -> Option<GameInfo> {
+    let selected = SELECTED_INSTALL.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    pick_install(detect_all(), selected.as_deref())
+}
+
+/// Detect all STFC installations on this machine, reusing the cached result if available.
+///
+/// Order: [`GAME_PATH_ENV`], then the platform detection (Xsolla launcher, Steam, default locations), without
+/// duplicates. Only a non-empty result is cached, so installing the game while Project Daystrom runs is picked up on
+/// the next call. The cache is invalidated wherever the installation may have changed:
+/// - after preparing, removing, or restoring the mod (entitlements or `version.dll` changed)
+/// - after launching the game (the game may update itself)
+/// - while and after the Scopely launcher runs (it installs updates, changing the version)
+/// - after selecting another installation
+pub fn detect_all() -> Vec<GameInfo> {
+    let mut cached = DETECTED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(installs) = cached.as_ref() {
+        return installs.clone();
+    }
+    let installs = detect_all_uncached();
+    if !installs.is_empty() {
+        *cached = Some(installs.clone());
+    }
+    installs
+}
+
+/// Full path to the Scopely launcher settings INI that detection reads the install directory from.
+///
+/// Returns `None` on platforms without game detection.
+pub fn launcher_settings_path() -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
-    let base = macos::detect();
+    return macos::launcher_settings_path();
+    #[cfg(target_os = "windows")]
+    return windows::launcher_settings_path();
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    None
+}
+
+/// Drop the cached detection result, so the next [`detect`] scans the filesystem again.
+pub fn invalidate_detection() {
+    log_debug!("Invalidating cached game detection");
+    *DETECTED.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Choose which installation [`detect`] returns when several exist, or reset to the default with `None`.
+///
+/// Returns an error if `install_dir` is not one of the detected installations.
+pub fn select_install(install_dir: Option<PathBuf>) -> Result<(), String> {
+    invalidate_detection();
+    if let Some(dir) = &install_dir {
+        if !detect_all().iter().any(|info| &info.install_dir == dir) {
+            return Err(format!("No STFC installation found at {}", dir.display()));
+        }
+        log_info!("Selected installation {}", dir.display());
+    } else {
+        log_info!("Installation selection reset");
+    }
+    *SELECTED_INSTALL.lock().unwrap_or_else(|e| e.into_inner()) = install_dir;
+    Ok(())
+}
+
+/// Return the selected installation if it is among `installs`, otherwise the first one.
+fn pick_install(installs: Vec<GameInfo>, selected: Option<&Path>) -> Option<GameInfo> {
+    if let Some(dir) = selected {
+        if let Some(info) = installs.iter().find(|info| info.install_dir == dir) {
+            return Some(info.clone());
+        }
+        log_warn!("Selected installation {} no longer found, using default", dir.display());
+    }
+    installs.into_iter().next()
+}
+
+/// Detect all STFC installations by scanning the filesystem.
+///
+/// Also reads the installed version from the `.version` file and, on macOS, the bundle version of each.
+fn detect_all_uncached() -> Vec<GameInfo> {
+    #[cfg(target_os = "macos")]
+    let found: Vec<(PathBuf, PathBuf)> = detect_override(macos::EXECUTABLE_REL).into_iter()
+        .chain(macos::detect_all())
+        .filter(|(_, executable)| version::is_stfc_bundle(executable))
+        .collect();
 
     #[cfg(target_os = "windows")]
-    let base = windows::detect();
+    let found: Vec<(PathBuf, PathBuf)> = detect_override(windows::EXECUTABLE_NAME).into_iter()
+        .chain(windows::detect_all())
+        .collect();
 
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    let base: Option<(PathBuf, PathBuf)> = {
+    #[cfg(target_os = "linux")]
+    let found: Vec<(PathBuf, PathBuf)> = detect_override(linux::EXECUTABLE_NAME).into_iter()
+        .chain(linux::detect_all())
+        .collect();
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let found: Vec<(PathBuf, PathBuf)> = {
         log::warn!("Game detection not implemented for this platform");
-        None
+        Vec::new()
     };
 
-    let (install_dir, executable) = base?;
+    let mut installs: Vec<GameInfo> = Vec::new();
+    for (install_dir, executable) in found {
+        if installs.iter().any(|info| info.install_dir == install_dir) {
+            continue;
+        }
+        installs.push(game_info(install_dir, executable));
+    }
+    installs
+}
+
+/// Build the [`GameInfo`] for an installation, reading its version information.
+fn game_info(install_dir: PathBuf, executable: PathBuf) -> GameInfo {
     let installed_version = version::read_installed(&install_dir);
-    Some(GameInfo { install_dir, executable, installed_version })
+    #[cfg(target_os = "macos")]
+    let bundle_version = version::read_bundle_version(&executable);
+    #[cfg(not(target_os = "macos"))]
+    let bundle_version = None;
+    GameInfo { install_dir, executable, installed_version, bundle_version }
+}
+
+/// Use the install directory from [`GAME_PATH_ENV`], if set and valid.
+///
+/// An invalid override is logged as a warning and ignored, so the regular detection still runs.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn detect_override(executable_rel: &str) -> Option<(PathBuf, PathBuf)> {
+    let raw = std::env::var_os(GAME_PATH_ENV)?;
+    let install_dir = PathBuf::from(raw);
+    log_debug!("{GAME_PATH_ENV} set to {}", install_dir.display());
+
+    let found = validate_install_dir(&install_dir, executable_rel);
+    if found.is_none() {
+        log_warn!(
+            "{GAME_PATH_ENV} ignored: no game executable at {}",
+            install_dir.join(executable_rel).display()
+        );
+    }
+    found
+}
+
+/// Return the install directory and executable path if the executable exists under `install_dir`.
+fn validate_install_dir(install_dir: &Path, executable_rel: &str) -> Option<(PathBuf, PathBuf)> {
+    let executable = install_dir.join(executable_rel);
+    executable.is_file().then(|| (install_dir.to_path_buf(), executable))
+}
+
+/// Resolve an explicitly chosen executable to its install directory and executable path.
+///
+/// Fails if the file does not exist or is not the STFC executable for this platform, so a stray path is never patched.
+pub fn install_for_executable(executable: &Path) -> Result<(PathBuf, PathBuf), GameError> {
+    #[cfg(target_os = "macos")]
+    let executable_rel = macos::EXECUTABLE_REL;
+    #[cfg(target_os = "windows")]
+    let executable_rel = windows::EXECUTABLE_NAME;
+    #[cfg(target_os = "linux")]
+    let executable_rel = linux::EXECUTABLE_NAME;
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    return Err(GameError::Failed("Game detection not implemented for this platform".into()));
+
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        let found = install_for_executable_rel(executable, executable_rel)?;
+        #[cfg(target_os = "macos")]
+        if !version::is_stfc_bundle(&found.1) {
+            return Err(GameError::Failed(format!("{} is not the STFC app bundle", executable.display())));
+        }
+        Ok(found)
+    }
+}
+
+/// Check that `executable` exists and ends with `executable_rel`, returning the install directory above it.
+fn install_for_executable_rel(executable: &Path, executable_rel: &str) -> Result<(PathBuf, PathBuf), GameError> {
+    if !executable.is_file() {
+        return Err(GameError::Failed(format!("{} does not exist", executable.display())));
+    }
+    let depth = Path::new(executable_rel).components().count();
+    match executable.ancestors().nth(depth) {
+        Some(install_dir) if executable.ends_with(executable_rel) => {
+            Ok((install_dir.to_path_buf(), executable.to_path_buf()))
+        }
+        _ => Err(GameError::Failed(format!("{} is not the STFC executable", executable.display()))),
+    }
+}
+
+/// Maximum time a process listing (`pgrep`/`tasklist`) may take before it is killed, so status polling cannot hang.
+const PROCESS_CHECK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Run a process listing command with [`PROCESS_CHECK_TIMEOUT`], logging a warning if it had to be killed.
+fn process_check_output(cmd: &mut Command) -> Option<Output> {
+    match output_with_timeout(cmd, PROCESS_CHECK_TIMEOUT) {
+        Ok(out) => Some(out),
+        Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+            log_warn!("Process check {:?} timed out: {e}", cmd.get_program());
+            None
+        }
+        Err(_) => None,
+    }
 }
 
 /// Check whether a process matching `pattern` is currently running.
 ///
 /// On Windows, filters `tasklist` by image name and checks stdout.
 /// On macOS/Linux, uses `pgrep -f` for full command-line matching.
+/// A listing that takes longer than [`PROCESS_CHECK_TIMEOUT`] counts as not running.
 fn is_process_active(pattern: &str) -> bool {
     #[cfg(target_os = "windows")]
     {
-        silent_command("tasklist")
-            .args(["/FI", &format!("IMAGENAME eq {pattern}"), "/NH"])
-            .output()
-            .map(|out| {
+        process_check_output(silent_command("tasklist").args(["/FI", &format!("IMAGENAME eq {pattern}"), "/NH"]))
+            .is_some_and(|out| {
                 out.status.success()
                     && String::from_utf8_lossy(&out.stdout).contains(pattern)
             })
-            .unwrap_or(false)
     }
 
     #[cfg(not(target_os = "windows"))]
     {
-        Command::new("pgrep")
-            .args(["-f", pattern])
-            .output()
-            .map(|out| out.status.success())
-            .unwrap_or(false)
+        process_check_output(Command::new("pgrep").args(["-f", pattern])).is_some_and(|out| out.status.success())
     }
 }
 
@@ -195,23 +622,82 @@ pub fn is_launcher_running() -> bool {
     is_process_active("Star Trek Fleet Command.app/Contents/MacOS/launcher")
 }
 
-/// Locate the bundled mod library in the app's resource directory.
-/// Returns `None` if the resource directory is unavailable or the library does not exist.
+/// Environment variable pointing at a locally built mod library, used instead of the bundled one.
+const MOD_LIBRARY_ENV: &str = "DAYSTROM_MOD_LIBRARY";
+
+/// File name of the mod library on this platform.
+#[cfg(target_os = "macos")]
+const MOD_LIBRARY_NAME: &str = "libstfc-community-patch.dylib";
+#[cfg(target_os = "windows")]
+const MOD_LIBRARY_NAME: &str = "stfc-community-patch.dll";
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const MOD_LIBRARY_NAME: &str = "libstfc-community-patch.so";
+
+/// Directories, relative to the resource directory, that may hold the bundled mod library, in probing order.
+///
+/// Bundled builds use `mod/`; some dev builds flatten the resources into the resource root.
+const MOD_LIBRARY_DIRS: &[&str] = &["mod", ""];
+
+/// Locate the mod library: [`MOD_LIBRARY_ENV`] first, then the bundled copy in the app's resource directory (see
+/// [`probe_mod_library`]).
+/// Returns `None` if neither the override nor the resource directory yields an existing library.
 pub fn find_mod_library(app: &tauri::AppHandle) -> Option<PathBuf> {
-    let resource_dir = app.path().resource_dir().ok()?;
+    if let Some(library) = overridden_mod_library() {
+        log_debug!("Using mod library from {MOD_LIBRARY_ENV}: {}", library.display());
+        return Some(library);
+    }
 
-    #[cfg(target_os = "macos")]
-    let library = resource_dir.join("mod/libstfc-community-patch.dylib");
-    #[cfg(target_os = "windows")]
-    let library = resource_dir.join("mod/stfc-community-patch.dll");
-    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-    let library = resource_dir.join("mod/libstfc-community-patch.so");
+    let library = bundled_mod_library(app)?;
+    log_debug!("Using bundled mod library: {}", library.display());
+    Some(library)
+}
 
-    if library.exists() {
-        Some(library)
-    } else {
-        None
+/// The mod library set via [`MOD_LIBRARY_ENV`], if the override is valid (see [`mod_library_override`]).
+pub fn overridden_mod_library() -> Option<PathBuf> {
+    std::env::var_os(MOD_LIBRARY_ENV).and_then(|raw| mod_library_override(Path::new(&raw)))
+}
+
+/// The mod library bundled in the app's resource directory, ignoring [`MOD_LIBRARY_ENV`].
+pub fn bundled_mod_library(app: &tauri::AppHandle) -> Option<PathBuf> {
+    probe_mod_library(&app.path().resource_dir().ok()?)
+}
+
+/// Return the first existing [`MOD_LIBRARY_NAME`] in the [`MOD_LIBRARY_DIRS`] below `resource_dir`.
+fn probe_mod_library(resource_dir: &Path) -> Option<PathBuf> {
+    MOD_LIBRARY_DIRS
+        .iter()
+        .map(|dir| resource_dir.join(dir).join(MOD_LIBRARY_NAME))
+        .find(|library| library.is_file())
+}
+
+/// Name of the file next to the mod library that holds the mod's version, written by `pnpm build:mod`.
+const MOD_VERSION_FILE: &str = "version.txt";
+
+/// Read the version of the mod library from the [`MOD_VERSION_FILE`] in the same directory.
+///
+/// Returns `None` if the file is missing or empty, e.g. for a library built outside `pnpm build:mod`.
+pub fn mod_version(library: &Path) -> Option<String> {
+    let path = library.parent()?.join(MOD_VERSION_FILE);
+    let version = std::fs::read_to_string(&path)
+        .map_err(|e| log_debug!("No mod version at {}: {e}", path.display()))
+        .ok()?;
+    let version = version.trim();
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// Validate a [`MOD_LIBRARY_ENV`] override: it must be an absolute path to an existing file.
+///
+/// An invalid override is logged as a warning and ignored, so the bundled library is used instead.
+fn mod_library_override(library: &Path) -> Option<PathBuf> {
+    if !library.is_absolute() {
+        log_warn!("{MOD_LIBRARY_ENV} ignored: not an absolute path: {}", library.display());
+        return None;
     }
+    if !library.is_file() {
+        log_warn!("{MOD_LIBRARY_ENV} ignored: no file at {}", library.display());
+        return None;
+    }
+    Some(library.to_path_buf())
 }
 
 /// Compute the SHA-256 digest of a file by streaming it in 8 KB chunks.
@@ -312,25 +798,19 @@ pub fn remove_mod(install_dir: &Path) -> Result<(), String> {
     Ok(())
 }
 
-/// Check whether the STFC game process is currently running.
+/// Check whether the STFC game process of the selected installation is currently running.
 ///
-/// On Windows, uses a two-stage check (image name + full path verification) to avoid false positives from unrelated
-/// processes named `prime.exe`.
+/// Resolves the executable through the cached [`detect`] on each call, so a changed selection or a rescan is picked up,
+/// and checks it with [`is_running`]. Returns `false` if no game is detected.
 pub fn is_game_running() -> bool {
-    #[cfg(target_os = "windows")]
-    {
-        let path = GAME_PATH.get_or_init(|| detect().map(|info| info.executable));
-        let Some(path) = path.as_ref() else { return false };
-        is_verified_process_running("prime.exe", path)
-    }
-    #[cfg(not(target_os = "windows"))]
-    is_process_active("Star Trek Fleet Command.app/Contents/MacOS/Star Trek Fleet Command")
+    detect().is_some_and(|info| is_running(&info.executable))
 }
 
 /// Check whether a process matching the given executable path is currently running.
 ///
 /// On Windows, uses a two-stage check (image name + full path verification).
-/// On macOS/Linux, uses `pgrep -f` for full command-line matching.
+/// On macOS, uses `pgrep -f` with [`process_pattern`], so only processes started from exactly this path (or its
+/// `.app` bundle) match. On Linux, the game runs under Proton/Wine, see [`game_process_pattern`].
 pub fn is_running(executable: &Path) -> bool {
     let name = executable.file_name().and_then(|n| n.to_str()).unwrap_or("");
     if name.is_empty() {
@@ -339,7 +819,91 @@ pub fn is_running(executable: &Path) -> bool {
     #[cfg(target_os = "windows")]
     return is_verified_process_running(name, executable);
     #[cfg(not(target_os = "windows"))]
-    is_process_active(name)
+    is_process_active(&game_process_pattern(executable))
+}
+
+/// Process flag the kernel sets on processes translated by Rosetta (`P_TRANSLATED` in `sys/proc.h`).
+const P_TRANSLATED: u32 = 0x0002_0000;
+
+/// Report how the running game process executes: `native`, or `rosetta` when an x86_64 game is translated on Apple
+/// Silicon, where an arm64-only mod library would not be injected.
+///
+/// Looks up the process via the same pattern as [`is_running`] and reads its flags with `ps -o flags=` (macOS only).
+/// Returns `None` if the game is not running, the flags cannot be read, or on other platforms.
+#[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+pub fn game_arch(executable: &Path) -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        let pgrep = process_check_output(Command::new("pgrep").args(["-f", &game_process_pattern(executable)]))?;
+        let stdout = String::from_utf8_lossy(&pgrep.stdout);
+        let pid = stdout.lines().next()?.trim().to_string();
+        let ps = Command::new("ps").args(["-p", &pid, "-o", "flags="]).output().ok()?;
+        let Some(translated) = parse_translated(&String::from_utf8_lossy(&ps.stdout)) else {
+            log_debug!("Could not read process flags of PID {pid}");
+            return None;
+        };
+        Some(if translated { "rosetta" } else { "native" }.to_string())
+    }
+    #[cfg(not(target_os = "macos"))]
+    None
+}
+
+/// Check the [`P_TRANSLATED`] bit in the hexadecimal process flags printed by `ps -o flags=`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_translated(flags: &str) -> Option<bool> {
+    let flags = u32::from_str_radix(flags.trim(), 16).ok()?;
+    Some(flags & P_TRANSLATED != 0)
+}
+
+/// Build the `pgrep -f`/`pkill -f` pattern for the game process started from `executable` on this platform.
+///
+/// Under Proton, Wine rewrites the command line, so Linux matches the executable's file name only; elsewhere the
+/// full path is matched via [`process_pattern`].
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn game_process_pattern(executable: &Path) -> String {
+    #[cfg(target_os = "linux")]
+    return linux::process_pattern(executable);
+    #[cfg(not(target_os = "linux"))]
+    process_pattern(executable)
+}
+
+/// Characters with a special meaning in POSIX extended regular expressions, as used by `pgrep`/`pkill`.
+#[cfg(not(target_os = "windows"))]
+const ERE_SPECIAL: &[char] = &['\\', '.', '[', ']', '(', ')', '{', '}', '*', '+', '?', '^', '$', '|'];
+
+/// Build the `pgrep -f`/`pkill -f` pattern for processes started from exactly `executable`.
+///
+/// Escapes regex metacharacters in the path and anchors it to the start of the command line, followed by either an
+/// argument separator or the end of the line. If the executable lives inside an `.app` bundle, the bundle path is
+/// accepted as well, since processes started via `open` sometimes show it instead of the inner binary. Processes
+/// that merely mention the path in their arguments (log viewers, helpers with a longer name) do not match.
+#[cfg(not(target_os = "windows"))]
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+pub(crate) fn process_pattern(executable: &Path) -> String {
+    let escaped = escape_ere(&executable.to_string_lossy());
+    match app_bundle(executable) {
+        Some(bundle) => format!("^({escaped}|{})( |$)", escape_ere(&bundle.to_string_lossy())),
+        None => format!("^{escaped}( |$)"),
+    }
+}
+
+/// Escape POSIX extended regular expression metacharacters in `text`.
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn escape_ere(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        if ERE_SPECIAL.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Return the `.app` bundle containing `path` (an executable or any other file inside the bundle), if any.
+#[cfg(not(target_os = "windows"))]
+pub fn app_bundle(path: &Path) -> Option<&Path> {
+    path.ancestors().skip(1).find(|p| p.extension().is_some_and(|ext| ext == "app"))
 }
 
 // ---- Tests ----
@@ -348,6 +912,114 @@ pub fn is_running(executable: &Path) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn output_with_timeout_returns_output() {
+        let out = output_with_timeout(Command::new("echo").arg("hello"), Duration::from_secs(5)).unwrap();
+        assert!(out.status.success());
+        assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "hello");
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn output_with_timeout_kills_overrunning_child() {
+        let start = Instant::now();
+        let err = output_with_timeout(Command::new("sleep").arg("5"), Duration::from_millis(100)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(2), "should not wait for the child");
+    }
+
+    #[test]
+    fn game_error_messages() {
+        assert_eq!(GameError::NotInstalled.to_string(), "STFC not found");
+        assert_eq!(GameError::GameRunning.to_string(), "Game is already running");
+        assert_eq!(
+            GameError::MissingEntitlements(vec!["get-task-allow".into(), "cs.disable-library-validation".into()])
+                .to_string(),
+            "Missing entitlements: get-task-allow, cs.disable-library-validation — patch them first"
+        );
+        assert_eq!(GameError::Failed("Failed to stop game".into()).to_string(), "Failed to stop game");
+    }
+
+    #[test]
+    fn game_error_serialises_kind_and_detail() {
+        assert_eq!(serde_json::to_value(GameError::GameRunning).unwrap(), serde_json::json!({"kind": "game_running"}));
+        assert_eq!(
+            serde_json::to_value(GameError::Codesign("failed".into())).unwrap(),
+            serde_json::json!({"kind": "codesign", "detail": "failed"})
+        );
+    }
+
+    #[test]
+    fn normalise_game_path_double_slash() {
+        assert_eq!(normalise_game_path("//Users/me/Games/STFC/"), "/Users/me/Games/STFC/");
+    }
+
+    #[test]
+    fn normalise_game_path_triple_slash() {
+        assert_eq!(normalise_game_path("///opt/stfc/"), "/opt/stfc/");
+    }
+
+    #[test]
+    fn normalise_game_path_quoted() {
+        assert_eq!(normalise_game_path("\"/Users/me/Games/STFC/\" "), "/Users/me/Games/STFC/");
+    }
+
+    #[test]
+    fn normalise_game_path_unchanged() {
+        assert_eq!(normalise_game_path("/opt/stfc/"), "/opt/stfc/");
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn normalise_game_path_backslashes() {
+        assert_eq!(normalise_game_path("\\\\Users\\me\\Games\\STFC\\"), "/Users/me/Games/STFC/");
+    }
+
+    #[test]
+    fn normalise_separators_to_platform() {
+        let sep = std::path::MAIN_SEPARATOR;
+        assert_eq!(normalise_separators("C:\\Games/STFC\\game"), format!("C:{sep}Games{sep}STFC{sep}game"));
+    }
+
+    #[test]
+    fn normalise_separators_keeps_platform_paths() {
+        let path = format!("{0}Users{0}me{0}STFC{0}", std::path::MAIN_SEPARATOR);
+        assert_eq!(normalise_separators(&path), path);
+    }
+
+    fn install(dir: &str) -> GameInfo {
+        GameInfo {
+            install_dir: PathBuf::from(dir),
+            executable: PathBuf::from(dir).join("prime"),
+            installed_version: None,
+            bundle_version: None,
+        }
+    }
+
+    #[test]
+    fn pick_install_defaults_to_first() {
+        let picked = pick_install(vec![install("/xsolla"), install("/steam")], None).unwrap();
+        assert_eq!(picked.install_dir, PathBuf::from("/xsolla"));
+    }
+
+    #[test]
+    fn pick_install_honours_selection() {
+        let picked = pick_install(vec![install("/xsolla"), install("/steam")], Some(Path::new("/steam"))).unwrap();
+        assert_eq!(picked.install_dir, PathBuf::from("/steam"));
+    }
+
+    #[test]
+    fn pick_install_ignores_stale_selection() {
+        let picked = pick_install(vec![install("/xsolla")], Some(Path::new("/gone"))).unwrap();
+        assert_eq!(picked.install_dir, PathBuf::from("/xsolla"));
+    }
+
+    #[test]
+    fn pick_install_none_found() {
+        assert!(pick_install(Vec::new(), None).is_none());
+    }
+
     #[test]
     fn read_game_path_normal() {
         let ini = "[General]\n152033..GAME_PATH=C:/Games/STFC/\n";
@@ -385,4 +1057,188 @@ REGION_INFO=\"@Variant(\\0\\0\\0\\b\\0\\0)\"
 LANGUAGE=de";
         assert_eq!(read_game_path(ini), Some("C:/Games/STFC/"));
     }
+
+    #[test]
+    fn validate_install_dir_with_executable() {
+        let dir = std::env::temp_dir().join("daystrom_test_override_valid");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("Game.app/Contents/MacOS")).unwrap();
+        std::fs::write(dir.join("Game.app/Contents/MacOS/Game"), "").unwrap();
+
+        assert_eq!(
+            validate_install_dir(&dir, "Game.app/Contents/MacOS/Game"),
+            Some((dir.clone(), dir.join("Game.app/Contents/MacOS/Game")))
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn validate_install_dir_missing_executable() {
+        let dir = std::env::temp_dir().join("daystrom_test_override_missing");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(validate_install_dir(&dir, "prime.exe"), None);
+        assert_eq!(validate_install_dir(&dir.join("nonexistent"), "prime.exe"), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn validate_install_dir_rejects_directory_as_executable() {
+        let dir = std::env::temp_dir().join("daystrom_test_override_dir");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("prime.exe")).unwrap();
+
+        assert_eq!(validate_install_dir(&dir, "prime.exe"), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn install_for_executable_resolves_install_dir() {
+        let dir = std::env::temp_dir().join("daystrom_test_explicit_executable");
+        let _ = std::fs::remove_dir_all(&dir);
+        let rel = "Game.app/Contents/MacOS/Game";
+        let executable = dir.join(rel);
+        std::fs::create_dir_all(executable.parent().unwrap()).unwrap();
+        std::fs::write(&executable, "").unwrap();
+        std::fs::write(dir.join("other.exe"), "").unwrap();
+
+        assert_eq!(install_for_executable_rel(&executable, rel).unwrap(), (dir.clone(), executable.clone()));
+        assert!(matches!(install_for_executable_rel(&dir.join("other.exe"), rel), Err(GameError::Failed(_))));
+        assert!(matches!(install_for_executable_rel(&dir.join("missing"), "missing"), Err(GameError::Failed(_))));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn mod_version_reads_sibling_file() {
+        let dir = std::env::temp_dir().join("daystrom_test_mod_version");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let library = dir.join("libstfc-community-patch.dylib");
+
+        assert_eq!(mod_version(&library), None);
+
+        std::fs::write(dir.join(MOD_VERSION_FILE), "  \n").unwrap();
+        assert_eq!(mod_version(&library), None);
+
+        std::fs::write(dir.join(MOD_VERSION_FILE), "1.0.0.0\n").unwrap();
+        assert_eq!(mod_version(&library), Some("1.0.0.0".to_string()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn mod_library_override_existing_file() {
+        let dir = std::env::temp_dir().join("daystrom_test_mod_library");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let library = dir.join("libstfc-community-patch.dylib");
+        std::fs::write(&library, "").unwrap();
+
+        assert_eq!(mod_library_override(&library), Some(library.clone()));
+        assert_eq!(mod_library_override(&dir.join("missing.dylib")), None);
+        assert_eq!(mod_library_override(&dir), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parse_translated_reads_flag_bit() {
+        assert_eq!(parse_translated("  4004\n"), Some(false));
+        assert_eq!(parse_translated("24004"), Some(true));
+        assert_eq!(parse_translated(""), None);
+        assert_eq!(parse_translated("flags"), None);
+    }
+
+    #[test]
+    fn probe_mod_library_prefers_mod_dir() {
+        let dir = std::env::temp_dir().join("daystrom_test_probe_mod_library");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("mod")).unwrap();
+
+        assert_eq!(probe_mod_library(&dir), None);
+
+        let flat = dir.join(MOD_LIBRARY_NAME);
+        std::fs::write(&flat, "").unwrap();
+        assert_eq!(probe_mod_library(&dir), Some(flat));
+
+        let nested = dir.join("mod").join(MOD_LIBRARY_NAME);
+        std::fs::write(&nested, "").unwrap();
+        assert_eq!(probe_mod_library(&dir), Some(nested));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn mod_library_override_rejects_relative_path() {
+        assert_eq!(mod_library_override(Path::new("target/libstfc-community-patch.dylib")), None);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn process_pattern_anchors_full_path() {
+        let exe = Path::new("/Games/STFC/Star Trek Fleet Command.app/Contents/MacOS/Star Trek Fleet Command");
+        assert_eq!(
+            process_pattern(exe),
+            "^(/Games/STFC/Star Trek Fleet Command\\.app/Contents/MacOS/Star Trek Fleet Command\
+            |/Games/STFC/Star Trek Fleet Command\\.app)( |$)"
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn app_bundle_of_inner_executable() {
+        let exe = Path::new("/Games/STFC/Star Trek Fleet Command.app/Contents/MacOS/Star Trek Fleet Command");
+        assert_eq!(app_bundle(exe), Some(Path::new("/Games/STFC/Star Trek Fleet Command.app")));
+        assert_eq!(app_bundle(Path::new("/opt/stfc/prime")), None);
+        assert_eq!(app_bundle(Path::new("/Games/prime.app")), None);
+
+        let bundled = Path::new("/Applications/Project Daystrom.app/Contents/Resources/mod/libmod.dylib");
+        assert_eq!(app_bundle(bundled), Some(Path::new("/Applications/Project Daystrom.app")));
+        assert_eq!(app_bundle(Path::new("/Users/jane/mods/libstfc-community-patch.dylib")), None);
+    }
+
+    /// Match `command_line` against [`process_pattern`] with `grep -E`, the same ERE dialect `pgrep` uses.
+    #[cfg(not(target_os = "windows"))]
+    fn pattern_matches(executable: &Path, command_line: &str) -> bool {
+        Command::new("grep")
+            .args(["-Eq", &process_pattern(executable)])
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                use std::io::Write;
+                child.stdin.take().unwrap().write_all(format!("{command_line}\n").as_bytes())?;
+                child.wait()
+            })
+            .unwrap()
+            .success()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn process_pattern_matches_both_command_line_forms() {
+        let exe = Path::new("/Games/STFC/Star Trek Fleet Command.app/Contents/MacOS/Star Trek Fleet Command");
+        let inner = exe.to_str().unwrap();
+        assert!(pattern_matches(exe, inner));
+        assert!(pattern_matches(exe, &format!("{inner} -x")));
+        assert!(pattern_matches(exe, "/Games/STFC/Star Trek Fleet Command.app"));
+        assert!(pattern_matches(exe, "/Games/STFC/Star Trek Fleet Command.app -psn_0_12345"));
+        assert!(!pattern_matches(exe, "/Games/STFC/Star Trek Fleet Command.app/Contents/MacOS/launcher"));
+        assert!(!pattern_matches(exe, "tail -f /Games/STFC/Star Trek Fleet Command.app"));
+        assert!(!pattern_matches(exe, "/Games/STFC/Star Trek Fleet Command.apps"));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn process_pattern_escapes_regex_characters() {
+        let exe = Path::new("/Users/jane (old)/Games [v2]/a+b/game$1^x|y?*{2}\\z");
+        assert_eq!(
+            process_pattern(exe),
+            "^/Users/jane \\(old\\)/Games \\[v2\\]/a\\+b/game\\$1\\^x\\|y\\?\\*\\{2\\}\\\\z( |$)"
+        );
+    }
 }
@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+
+use crate::use_log;
+
+use_log!("GameDetect");
+
+/// Display name of STFC as written into Steam's `appmanifest_*.acf` files.
+const STEAM_APP_NAME: &str = "Star Trek Fleet Command";
+
+/// Path to the Steam library index, relative to the Steam root directory.
+const LIBRARY_FOLDERS_REL: &str = "steamapps/libraryfolders.vdf";
+
+/// Split a single VDF/ACF line into its quoted key and value.
+///
+/// Returns `None` for section headers, braces, and lines with fewer than two quoted tokens.
+/// Escaped backslashes (`\\`) in the value are unescaped, so Windows paths come out as written on disk.
+fn parse_kv(line: &str) -> Option<(&str, String)> {
+    let mut tokens = line.split('"').skip(1).step_by(2);
+    let key = tokens.next()?;
+    let value = tokens.next()?;
+    Some((key, value.replace("\\\\", "\\")))
+}
+
+/// Extract all library root paths from the content of `libraryfolders.vdf`.
+///
+/// Hand-rolled like [`super::read_game_path`]: only the `"path"` entries are of interest, so a full VDF parser
+/// would be overkill.
+fn parse_library_paths(content: &str) -> Vec<PathBuf> {
+    content
+        .lines()
+        .filter_map(parse_kv)
+        .filter(|(key, _)| *key == "path")
+        .map(|(_, value)| PathBuf::from(value))
+        .collect()
+}
+
+/// Read the value of `key` from the content of an `appmanifest_*.acf` file.
+fn read_manifest_value(content: &str, key: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(parse_kv)
+        .find(|(k, _)| *k == key)
+        .map(|(_, value)| value)
+}
+
+/// Search a single Steam library for the STFC app manifest and return its install directory.
+fn find_in_library(library: &Path) -> Option<PathBuf> {
+    let steamapps = library.join("steamapps");
+    let entries = std::fs::read_dir(&steamapps)
+        .map_err(|e| log_debug!("Could not read {}: {e}", steamapps.display()))
+        .ok()?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with("appmanifest_") || !name.ends_with(".acf") {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(entry.path()) else { continue };
+        if read_manifest_value(&content, "name").as_deref() != Some(STEAM_APP_NAME) {
+            continue;
+        }
+        let install_dir = read_manifest_value(&content, "installdir")?;
+        return Some(steamapps.join("common").join(install_dir));
+    }
+    None
+}
+
+/// Locate a Steam-installed STFC by scanning all libraries listed in `libraryfolders.vdf`.
+///
+/// `steam_root` is the platform-specific Steam directory; `executable_rel` is the game executable relative to the
+/// install dir. The Steam root itself is always searched, even if `libraryfolders.vdf` is missing.
+/// Returns `None` (with debug/warn logging) if no library contains STFC or the executable does not exist on disk.
+pub fn detect(steam_root: &Path, executable_rel: &str) -> Option<(PathBuf, PathBuf)> {
+    let vdf_path = steam_root.join(LIBRARY_FOLDERS_REL);
+    log_debug!("Looking for Steam libraries at {}", vdf_path.display());
+
+    let mut libraries = vec![steam_root.to_path_buf()];
+    match std::fs::read_to_string(&vdf_path) {
+        Ok(content) => {
+            for path in parse_library_paths(&content) {
+                if !libraries.contains(&path) {
+                    libraries.push(path);
+                }
+            }
+        }
+        Err(e) => log_debug!("Could not read Steam library folders: {e}"),
+    }
+
+    let install_dir = libraries.iter().find_map(|library| find_in_library(library))?;
+    let executable = install_dir.join(executable_rel);
+
+    if !executable.exists() {
+        log_warn!(
+            "Steam install directory found but executable missing: {}",
+            executable.display()
+        );
+        return None;
+    }
+
+    Some((install_dir, executable))
+}
+
+// ---- Tests ----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIBRARY_FOLDERS: &str = r#""libraryfolders"
+{
+	"0"
+	{
+		"path"		"C:\\Program Files (x86)\\Steam"
+		"label"		""
+		"apps"
+		{
+			"228980"		"12345"
+		}
+	}
+	"1"
+	{
+		"path"		"D:\\SteamLibrary"
+		"label"		"Games"
+	}
+}"#;
+
+    const APP_MANIFEST: &str = r#""AppState"
+{
+	"appid"		"1234567"
+	"name"		"Star Trek Fleet Command"
+	"installdir"		"Star Trek Fleet Command"
+}"#;
+
+    #[test]
+    fn parse_kv_quoted_pair() {
+        assert_eq!(parse_kv("\t\t\"label\"\t\t\"Games\""), Some(("label", "Games".to_string())));
+    }
+
+    #[test]
+    fn parse_kv_section_header() {
+        assert_eq!(parse_kv("\t\"0\""), None);
+        assert_eq!(parse_kv("{"), None);
+    }
+
+    #[test]
+    fn parse_library_paths_unescapes_backslashes() {
+        assert_eq!(
+            parse_library_paths(LIBRARY_FOLDERS),
+            vec![
+                PathBuf::from("C:\\Program Files (x86)\\Steam"),
+                PathBuf::from("D:\\SteamLibrary"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_library_paths_empty_content() {
+        assert!(parse_library_paths("").is_empty());
+    }
+
+    #[test]
+    fn read_manifest_value_name_and_installdir() {
+        assert_eq!(read_manifest_value(APP_MANIFEST, "name").as_deref(), Some(STEAM_APP_NAME));
+        assert_eq!(read_manifest_value(APP_MANIFEST, "installdir").as_deref(), Some("Star Trek Fleet Command"));
+    }
+
+    #[test]
+    fn read_manifest_value_missing_key() {
+        assert_eq!(read_manifest_value(APP_MANIFEST, "buildid"), None);
+    }
+
+    #[test]
+    fn detect_finds_game_in_secondary_library() {
+        let root = std::env::temp_dir().join("daystrom_test_steam_detect");
+        let _ = std::fs::remove_dir_all(&root);
+        let steam = root.join("Steam");
+        let library = root.join("Library");
+        std::fs::create_dir_all(steam.join("steamapps")).unwrap();
+        std::fs::create_dir_all(library.join("steamapps/common/Star Trek Fleet Command")).unwrap();
+
+        let vdf = format!("\"libraryfolders\"\n{{\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}", library.display());
+        std::fs::write(steam.join(LIBRARY_FOLDERS_REL), vdf).unwrap();
+        std::fs::write(library.join("steamapps/appmanifest_1234567.acf"), APP_MANIFEST).unwrap();
+        std::fs::write(library.join("steamapps/common/Star Trek Fleet Command/prime.exe"), "").unwrap();
+
+        let (install_dir, executable) = detect(&steam, "prime.exe").unwrap();
+        assert_eq!(install_dir, library.join("steamapps/common/Star Trek Fleet Command"));
+        assert_eq!(executable, install_dir.join("prime.exe"));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn detect_none_without_manifest() {
+        let root = std::env::temp_dir().join("daystrom_test_steam_none");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("steamapps")).unwrap();
+
+        assert!(detect(&root, "prime.exe").is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
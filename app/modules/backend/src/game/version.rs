@@ -38,6 +38,85 @@ fn parse_version_string(content: &str) -> Option<u32> {
     None
 }
 
+// ---- Bundle Version -------------------------------------------------------------
+
+/// Read the marketing version (`CFBundleShortVersionString`) from the app bundle's `Info.plist`.
+///
+/// `executable` is the binary inside `<name>.app/Contents/MacOS/`. Returns `None` (with debug logging) if the plist
+/// is missing, binary-encoded, or lacks the key — detection must still succeed without it.
+#[cfg(target_os = "macos")]
+pub fn read_bundle_version(executable: &Path) -> Option<String> {
+    let plist = executable.parent()?.parent()?.join("Info.plist");
+    let content = std::fs::read_to_string(&plist)
+        .map_err(|e| log_debug!("Could not read {}: {e}", plist.display()))
+        .ok()?;
+
+    let version = parse_plist_string(&content, "CFBundleShortVersionString");
+    if version.is_none() {
+        log_debug!("No CFBundleShortVersionString in {}", plist.display());
+    }
+    version
+}
+
+/// Bundle identifier of the real STFC app bundle.
+const STFC_BUNDLE_ID: &str = "com.scopely.startrek";
+
+/// Check that `executable` sits in a genuine STFC app bundle, judged by the `CFBundleIdentifier` in `Info.plist`.
+///
+/// Rejects (with warn logging) a bundle without `Info.plist` or with any identifier other than [`STFC_BUNDLE_ID`],
+/// e.g. a stale directory holding a same-named stub. A plist that cannot be read or lacks the key is rejected as
+/// well, since the bundle cannot be confirmed as STFC then.
+#[cfg(target_os = "macos")]
+pub fn is_stfc_bundle(executable: &Path) -> bool {
+    let Some(plist) = executable.parent().and_then(Path::parent).map(|contents| contents.join("Info.plist")) else {
+        return false;
+    };
+    if !plist.is_file() {
+        log_warn!("Ignoring {}: no Info.plist at {}", executable.display(), plist.display());
+        return false;
+    }
+    let Ok(content) = std::fs::read_to_string(&plist) else {
+        log_warn!("Ignoring {}: could not read {}", executable.display(), plist.display());
+        return false;
+    };
+    match bundle_identifier(&content) {
+        Some(id) if is_stfc_bundle_id(&id) => true,
+        Some(id) => {
+            log_warn!("Ignoring {}: bundle identifier {id} does not belong to STFC", executable.display());
+            false
+        }
+        None => {
+            log_warn!("Ignoring {}: no CFBundleIdentifier in {}", executable.display(), plist.display());
+            false
+        }
+    }
+}
+
+/// Read the `CFBundleIdentifier` from the content of an XML `Info.plist`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn bundle_identifier(xml: &str) -> Option<String> {
+    parse_plist_string(xml, "CFBundleIdentifier")
+}
+
+/// Whether a bundle identifier is exactly [`STFC_BUNDLE_ID`].
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn is_stfc_bundle_id(id: &str) -> bool {
+    id == STFC_BUNDLE_ID
+}
+
+/// Extract the `<string>` value that follows `<key>{key}</key>` in an XML plist.
+///
+/// Hand-rolled like the entitlement check; returns `None` if the key is absent or not followed by a string.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_plist_string(xml: &str, key: &str) -> Option<String> {
+    let needle = format!("<key>{key}</key>");
+    let pos = xml.find(&needle)?;
+    let rest = xml[pos + needle.len()..].trim_start().strip_prefix("<string>")?;
+    let end = rest.find("</string>")?;
+    let value = rest[..end].trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
 // ---- Remote Version -------------------------------------------------------------
 
 /// Fetch the latest game version from the Scopely update API.
@@ -176,6 +255,109 @@ mod tests {
         assert!(parse_update_response(xml).is_err());
     }
 
+    // -- parse_plist_string --
+
+    #[test]
+    fn plist_string_normal() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>CFBundleExecutable</key>
+    <string>Star Trek Fleet Command</string>
+    <key>CFBundleShortVersionString</key>
+    <string>1.000.41234</string>
+</dict>
+</plist>"#;
+        assert_eq!(parse_plist_string(xml, "CFBundleShortVersionString").as_deref(), Some("1.000.41234"));
+    }
+
+    #[test]
+    fn plist_string_missing_key() {
+        let xml = "<dict><key>CFBundleExecutable</key><string>x</string></dict>";
+        assert_eq!(parse_plist_string(xml, "CFBundleShortVersionString"), None);
+    }
+
+    #[test]
+    fn plist_string_non_string_value() {
+        let xml = "<dict><key>CFBundleShortVersionString</key><integer>5</integer></dict>";
+        assert_eq!(parse_plist_string(xml, "CFBundleShortVersionString"), None);
+    }
+
+    #[test]
+    fn plist_string_empty_value() {
+        let xml = "<dict><key>CFBundleShortVersionString</key><string> </string></dict>";
+        assert_eq!(parse_plist_string(xml, "CFBundleShortVersionString"), None);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn read_bundle_version_from_app_bundle() {
+        let dir = std::env::temp_dir().join("daystrom_test_bundle_version");
+        let _ = std::fs::remove_dir_all(&dir);
+        let macos_dir = dir.join("Game.app/Contents/MacOS");
+        std::fs::create_dir_all(&macos_dir).unwrap();
+        std::fs::write(
+            dir.join("Game.app/Contents/Info.plist"),
+            "<dict><key>CFBundleShortVersionString</key><string>1.2.3</string></dict>",
+        )
+        .unwrap();
+
+        assert_eq!(read_bundle_version(&macos_dir.join("Game")).as_deref(), Some("1.2.3"));
+        assert_eq!(read_bundle_version(&dir.join("missing/Contents/MacOS/Game")), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    // -- bundle identifier --
+
+    #[test]
+    fn bundle_identifier_from_plist() {
+        let xml = r#"<dict>
+    <key>CFBundleExecutable</key>
+    <string>Star Trek Fleet Command</string>
+    <key>CFBundleIdentifier</key>
+    <string>com.scopely.startrek</string>
+</dict>"#;
+        assert_eq!(bundle_identifier(xml).as_deref(), Some("com.scopely.startrek"));
+        assert_eq!(bundle_identifier("<dict></dict>"), None);
+    }
+
+    #[test]
+    fn stfc_bundle_id_check() {
+        assert!(is_stfc_bundle_id("com.scopely.startrek"));
+        assert!(!is_stfc_bundle_id("com.scopely."));
+        assert!(!is_stfc_bundle_id("com.scopely.otherapp"));
+        assert!(!is_stfc_bundle_id("com.scopely.startrek.stub"));
+        assert!(!is_stfc_bundle_id("com.example.stub"));
+        assert!(!is_stfc_bundle_id("com.stfcmod.startrekpatch"));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn is_stfc_bundle_checks_identifier() {
+        let dir = std::env::temp_dir().join("daystrom_test_bundle_identifier");
+        let _ = std::fs::remove_dir_all(&dir);
+        let write_bundle = |name: &str, id: &str| {
+            std::fs::create_dir_all(dir.join(format!("{name}.app/Contents/MacOS"))).unwrap();
+            std::fs::write(
+                dir.join(format!("{name}.app/Contents/Info.plist")),
+                format!("<dict><key>CFBundleIdentifier</key><string>{id}</string></dict>"),
+            )
+            .unwrap();
+            dir.join(format!("{name}.app/Contents/MacOS/{name}"))
+        };
+
+        assert!(is_stfc_bundle(&write_bundle("Real", "com.scopely.startrek")));
+        assert!(!is_stfc_bundle(&write_bundle("Stub", "com.example.stub")));
+        assert!(!is_stfc_bundle(&dir.join("Missing.app/Contents/MacOS/Missing")));
+
+        std::fs::create_dir_all(dir.join("NoId.app/Contents/MacOS")).unwrap();
+        std::fs::write(dir.join("NoId.app/Contents/Info.plist"), "<dict></dict>").unwrap();
+        assert!(!is_stfc_bundle(&dir.join("NoId.app/Contents/MacOS/NoId")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     // -- read_installed (filesystem) --
 
     #[test]
@@ -9,7 +9,7 @@ const LAUNCHER_SETTINGS_PATH: &str =
     "Star Trek Fleet Command/launcher_settings.ini";
 
 /// Name of the game executable on Windows.
-const EXECUTABLE_NAME: &str = "prime.exe";
+pub(super) const EXECUTABLE_NAME: &str = "prime.exe";
 
 /// Name of the Scopely launcher executable on Windows.
 const LAUNCHER_EXECUTABLE: &str = "launcher.exe";
@@ -17,17 +17,30 @@ const LAUNCHER_EXECUTABLE: &str = "launcher.exe";
 /// Path to the Scopely launcher directory, relative to `%LOCALAPPDATA%`.
 const LAUNCHER_DIR: &str = "Star Trek Fleet Command";
 
+/// Default game directory, relative to the launcher directory (the launcher sits two levels above `GAME_PATH`).
+const DEFAULT_GAME_DIR: &str = r"default\game";
+
 /// Registry uninstall key where the Scopely launcher registers itself.
 const UNINSTALL_REG_KEY: &str =
     r"HKCU\Software\Microsoft\Windows\CurrentVersion\Uninstall\Star Trek Fleet Command";
 
+/// Registry key where Steam stores its install location.
+const STEAM_REG_KEY: &str = r"HKCU\Software\Valve\Steam";
+
+/// Default Steam install location, used when the registry has no `SteamPath`.
+const STEAM_DEFAULT_ROOT: &str = r"C:\Program Files (x86)\Steam";
+
+/// Full path to the Scopely launcher settings INI, or `None` if `%LOCALAPPDATA%` is unknown.
+pub fn launcher_settings_path() -> Option<PathBuf> {
+    Some(dirs::data_local_dir()?.join(LAUNCHER_SETTINGS_PATH))
+}
+
 /// Read the game install directory from the Scopely launcher settings INI.
 ///
 /// Parses `%LOCALAPPDATA%\Star Trek Fleet Command\launcher_settings.ini` and extracts
 /// the `GAME_PATH` value. Returns `None` if the file or key is missing.
 fn read_install_dir() -> Option<PathBuf> {
-    let local_app_data = dirs::data_local_dir()?;
-    let ini_path = local_app_data.join(LAUNCHER_SETTINGS_PATH);
+    let ini_path = launcher_settings_path()?;
     log_debug!("Looking for launcher settings at {}", ini_path.display());
 
     let content = std::fs::read_to_string(&ini_path)
@@ -36,7 +49,7 @@ fn read_install_dir() -> Option<PathBuf> {
 
     let raw_path = super::read_game_path(&content)?;
     log_debug!("Raw GAME_PATH value: {raw_path}");
-    Some(PathBuf::from(raw_path))
+    Some(PathBuf::from(super::normalise_separators(raw_path)))
 }
 
 /// Locate the Scopely launcher executable on Windows.
@@ -82,34 +95,39 @@ pub fn find_launcher() -> Option<PathBuf> {
 }
 
 /// Query the Windows registry for the launcher's install location.
+fn find_launcher_via_registry() -> Option<PathBuf> {
+    let install_dir = PathBuf::from(query_registry_value(UNINSTALL_REG_KEY, "InstallLocation")?);
+    let launcher = install_dir.join(LAUNCHER_EXECUTABLE);
+    if launcher.exists() {
+        log_debug!("Found launcher via registry: {}", launcher.display());
+        return Some(launcher);
+    }
+    log_debug!("Registry path found but launcher missing: {}", launcher.display());
+    None
+}
+
+/// Read a `REG_SZ` value from the Windows registry.
 ///
 /// Uses `reg query` to avoid an external crate dependency.
-fn find_launcher_via_registry() -> Option<PathBuf> {
+fn query_registry_value(key: &str, name: &str) -> Option<String> {
     let output = super::silent_command("reg")
-        .args(["query", UNINSTALL_REG_KEY, "/v", "InstallLocation"])
+        .args(["query", key, "/v", name])
         .output()
         .map_err(|e| log_debug!("reg query failed: {e}"))
         .ok()?;
 
     if !output.status.success() {
-        log_debug!("reg query returned non-zero status");
+        log_debug!("reg query for {name} returned non-zero status");
         return None;
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     // Output format: "    InstallLocation    REG_SZ    C:\path\to\launcher"
     for line in stdout.lines() {
-        let trimmed = line.trim();
-        if let Some(rest) = trimmed.strip_prefix("InstallLocation") {
+        if let Some(rest) = line.trim().strip_prefix(name) {
             // Skip the "REG_SZ" type token and surrounding whitespace
             if let Some(value) = rest.trim().strip_prefix("REG_SZ") {
-                let install_dir = PathBuf::from(value.trim());
-                let launcher = install_dir.join(LAUNCHER_EXECUTABLE);
-                if launcher.exists() {
-                    log_debug!("Found launcher via registry: {}", launcher.display());
-                    return Some(launcher);
-                }
-                log_debug!("Registry path found but launcher missing: {}", launcher.display());
+                return Some(value.trim().to_string());
             }
         }
     }
@@ -117,11 +135,47 @@ fn find_launcher_via_registry() -> Option<PathBuf> {
     None
 }
 
+/// Return the Steam root directory from the registry, falling back to the default install location.
+fn steam_root() -> PathBuf {
+    query_registry_value(STEAM_REG_KEY, "SteamPath")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(STEAM_DEFAULT_ROOT))
+}
+
+/// Locate all STFC installations: the Xsolla launcher's install first, then Steam, then the launcher's default
+/// install location.
+///
+/// Returns install directory and executable path pairs in that order; empty if no source finds the game.
+pub fn detect_all() -> Vec<(PathBuf, PathBuf)> {
+    let mut found = Vec::new();
+    if let Some(install) = detect_xsolla() {
+        log_debug!("STFC detected via Xsolla launcher settings");
+        found.push(install);
+    }
+    if let Some(install) = super::steam::detect(&steam_root(), EXECUTABLE_NAME) {
+        log_debug!("STFC detected via Steam library");
+        found.push(install);
+    }
+    if let Some(install) = detect_default() {
+        log_debug!("STFC detected at the launcher's default install location");
+        found.push(install);
+    }
+    found
+}
+
+/// Check the Scopely launcher's default game directory (`%LOCALAPPDATA%\Star Trek Fleet Command\default\game`).
+///
+/// Catches installs whose `launcher_settings.ini` points elsewhere, e.g. after moving to another drive.
+fn detect_default() -> Option<(PathBuf, PathBuf)> {
+    let install_dir = dirs::data_local_dir()?.join(LAUNCHER_DIR).join(DEFAULT_GAME_DIR);
+    super::validate_install_dir(&install_dir, EXECUTABLE_NAME)
+}
+
 /// Locate the STFC installation by reading the Scopely launcher settings INI.
 ///
 /// Returns `None` (with debug/warn logging) if the settings file is missing,
 /// the game path key is absent, or the executable does not exist on disk.
-pub fn detect() -> Option<(PathBuf, PathBuf)> {
+fn detect_xsolla() -> Option<(PathBuf, PathBuf)> {
     let install_dir = read_install_dir()?;
     let executable = install_dir.join(EXECUTABLE_NAME);
 
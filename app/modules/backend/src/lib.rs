@@ -4,13 +4,20 @@ use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent}
 use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
 
 mod commands;
+mod diagnostics;
 mod game;
 mod logging;
 #[cfg(target_os = "macos")]
 mod macos_quit;
 mod monitor;
+mod settings_watcher;
 
-use commands::{get_game_status, launch_game, launch_updater, prepare_mod, remove_mod};
+use commands::{
+    can_patch, check_prerequisites, clear_logs, export_diagnostics, get_app_version, get_entitlements_xml,
+    get_game_status, get_log_buffer, get_mod_version, get_recent_logs, launch_game, launch_updater, launch_vanilla,
+    log_stats, open_log_dir, prepare_mod, prepare_mod_at, preview_patch, query_logs, readiness, relaunch_game,
+    remove_mod, restore_entitlements, reveal_game, rotate_now, select_install, stop_game, uninstall_mod, validate_dylib,
+};
 
 use_log!("Startup");
 
@@ -46,6 +53,18 @@ pub fn run() {
         .setup(|app| {
             let version = &app.package_info().version;
             log_info!("Project Daystrom {version} initialised");
+            let levels = logging::levels();
+            log_info!("Log level: stdout {}, file {}", levels.stdout, levels.file);
+            if logging::rotation_disabled() {
+                log_info!("Log rotation is disabled, archives are not cleaned up");
+            }
+            logging::attach_emitter(app.handle().clone());
+
+            // build.rs compares Cargo.toml against package.json; the cargo warning is hidden on cached builds
+            #[cfg(debug_assertions)]
+            if env!("DAYSTROM_VERSION_SYNCED") == "false" {
+                log_warn!("Version mismatch between Cargo.toml and package.json, rebuild needed");
+            }
 
             #[cfg(target_os = "macos")]
             {
@@ -54,6 +73,7 @@ pub fn run() {
             }
 
             monitor::start(app.handle().clone());
+            settings_watcher::start(app.handle().clone());
 
             // ---- System Tray --------------------------------------------------------
 
@@ -117,10 +137,34 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             get_game_status,
+            readiness,
             launch_updater,
             prepare_mod,
+            prepare_mod_at,
+            preview_patch,
             remove_mod,
+            uninstall_mod,
+            restore_entitlements,
             launch_game,
+            launch_vanilla,
+            relaunch_game,
+            stop_game,
+            open_log_dir,
+            get_app_version,
+            get_mod_version,
+            get_recent_logs,
+            query_logs,
+            get_log_buffer,
+            log_stats,
+            clear_logs,
+            rotate_now,
+            export_diagnostics,
+            select_install,
+            reveal_game,
+            check_prerequisites,
+            can_patch,
+            validate_dylib,
+            get_entitlements_xml,
         ])
         .on_window_event(|window, event| {
             match event {
@@ -167,6 +211,9 @@ pub fn run() {
                 }
                 tauri::RunEvent::Exit => {
                     log_debug!("[EVENT] Exit (app is shutting down)");
+                    settings_watcher::stop();
+                    log_info!("Project Daystrom shutting down");
+                    logging::shutdown();
                 }
                 _ => {}
             }
@@ -1,9 +1,22 @@
-use std::{fs, path::{Path, PathBuf}, sync::Mutex};
+use std::{
+    collections::VecDeque,
+    ffi::{OsStr, OsString},
+    fs,
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+#[cfg(not(target_os = "windows"))]
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use colored::Colorize;
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
 use log::{Level, LevelFilter};
+use serde::Serialize;
+use tauri::Emitter;
 use tauri::plugin::TauriPlugin;
 use tauri_plugin_log::{Builder, Target, TargetKind, TimezoneStrategy, fern};
+use ts_rs::TS;
 
 // ---- Macros (public API) --------------------------------------------------------
 
@@ -12,12 +25,17 @@ use tauri_plugin_log::{Builder, Target, TargetKind, TimezoneStrategy, fern};
 /// Generates `log_trace!`, `log_debug!`, `log_info!`, `log_warn!` and `log_error!` macros that automatically set
 /// the log target to the given name. The name appears in the `[loggerName]` column of the log output.
 ///
+/// Each macro has an `_as` variant (`log_info_as!` etc.) that takes a different logger name as its first argument,
+/// for the odd call in a module that covers several subsystems. All other calls keep the scope's name.
+///
 /// # Example
 ///
 /// ```ignore
 /// use_log!("Startup");
 /// log_info!("Project Daystrom {version} initialised");
 /// // → ... INFO [Startup] (Backend: lib.rs: 14): Project Daystrom 0.1.0 initialised
+/// log_warn_as!("Tray", "Tray icon unavailable");
+/// // → ... WARN [Tray] (Backend: lib.rs: 15): Tray icon unavailable
 /// ```
 #[macro_export]
 macro_rules! use_log {
@@ -42,6 +60,16 @@ macro_rules! __define_log_macros {
         macro_rules! log_warn { ($d ( $d arg:tt )*) => { ::log::warn!(target: $target, $d ( $d arg )*) }; }
         #[allow(unused_macros)]
         macro_rules! log_error { ($d ( $d arg:tt )*) => { ::log::error!(target: $target, $d ( $d arg )*) }; }
+        #[allow(unused_macros)]
+        macro_rules! log_trace_as { ($d name:expr, $d ( $d arg:tt )*) => { ::log::trace!(target: $d name, $d ( $d arg )*) }; }
+        #[allow(unused_macros)]
+        macro_rules! log_debug_as { ($d name:expr, $d ( $d arg:tt )*) => { ::log::debug!(target: $d name, $d ( $d arg )*) }; }
+        #[allow(unused_macros)]
+        macro_rules! log_info_as { ($d name:expr, $d ( $d arg:tt )*) => { ::log::info!(target: $d name, $d ( $d arg )*) }; }
+        #[allow(unused_macros)]
+        macro_rules! log_warn_as { ($d name:expr, $d ( $d arg:tt )*) => { ::log::warn!(target: $d name, $d ( $d arg )*) }; }
+        #[allow(unused_macros)]
+        macro_rules! log_error_as { ($d name:expr, $d ( $d arg:tt )*) => { ::log::error!(target: $d name, $d ( $d arg )*) }; }
     };
 }
 
@@ -50,46 +78,232 @@ macro_rules! __define_log_macros {
 /// Base name for log files (without extension).
 const LOG_FILE_NAME: &str = "project-daystrom";
 
+/// Base name for the optional JSON Lines log file (without extension).
+///
+/// Only truncated once it exceeds the size limit; the archiving and cleanup apply to [`LOG_FILE_NAME`] only.
+const JSON_LOG_FILE_NAME: &str = "project-daystrom-json";
+
+/// Default size at which the current log file is rotated (1 MB), see [`LOG_MAX_SIZE_ENV`].
+const MAX_LOG_FILE_SIZE: u64 = 1_000_000;
+
+/// Environment variable that overrides [`MAX_LOG_FILE_SIZE`], in kilobytes.
+const LOG_MAX_SIZE_ENV: &str = "DAYSTROM_LOG_MAX_SIZE_KB";
+
+/// Environment variable that enables the JSON Lines target when set to `1`.
+const LOG_JSON_ENV: &str = "DAYSTROM_LOG_JSON";
+
+/// Environment variable that additionally writes every record as JSON Lines to stderr when set to `1`, for log
+/// collectors; stdout keeps the human-readable format.
+const LOG_JSON_STDERR_ENV: &str = "DAYSTROM_LOG_JSON_STDERR";
+
+/// Environment variable that overrides the global log level (`trace`, `debug`, `info`, `warn`, or `error`).
+const LOG_LEVEL_ENV: &str = "DAYSTROM_LOG_LEVEL";
+
+/// Environment variable that overrides the log level of the console (stdout and the in-app log panel).
+const LOG_LEVEL_STDOUT_ENV: &str = "DAYSTROM_LOG_LEVEL_STDOUT";
+
+/// Environment variable that overrides the log level of the log files in the log directory.
+const LOG_LEVEL_FILE_ENV: &str = "DAYSTROM_LOG_LEVEL_FILE";
+
+/// Global log level when [`LOG_LEVEL_ENV`] is unset or invalid.
+const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Debug;
+
+/// Minimum levels of the console and file targets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogLevels {
+    /// Level for stdout, the optional JSON stderr stream, and the in-memory buffer behind the in-app log panel.
+    pub stdout: LevelFilter,
+    /// Level for the text and JSON log files.
+    pub file: LevelFilter,
+}
+
+/// Levels chosen in [`build_plugin`], see [`levels`].
+static LOG_LEVELS: OnceLock<LogLevels> = OnceLock::new();
+
 /// Build the tauri-plugin-log plugin with our custom format and targets.
 ///
 /// Performs log rotation before initializing the plugin, because the plugin opens its file handle in append mode,
-/// so renaming afterward would not take effect.
+/// so renaming afterward would not take effect. The archive retention window and the rotation period are read once
+/// from [`LOG_RETENTION_ENV`] and [`LOG_ROTATION_ENV`] and used for both startup and runtime rotation. The size limit
+/// from [`LOG_MAX_SIZE_ENV`] is enforced by the runtime rotation; the plugin's own size rotation is disabled. With
+/// [`NO_LOG_ROTATION_ENV`], neither rotation runs and [`ROTATION_STATE`] stays `None`.
+/// The global level comes from [`LOG_LEVEL_ENV`] and can be overridden per target by [`LOG_LEVEL_STDOUT_ENV`] and
+/// [`LOG_LEVEL_FILE_ENV`]; `tao` and `wry` stay at `Warn` regardless. Where there is a [`log_dir`], the log file is
+/// written by [`write_file_record`], which collapses repeated lines; elsewhere the plugin writes it unchanged.
 pub fn build_plugin() -> TauriPlugin<tauri::Wry> {
-    rotate_logs();
-    init_runtime_rotation();
+    let levels = *LOG_LEVELS.get_or_init(|| {
+        let global = log_level(std::env::var(LOG_LEVEL_ENV).ok().as_deref(), DEFAULT_LOG_LEVEL);
+        LogLevels {
+            stdout: log_level(std::env::var(LOG_LEVEL_STDOUT_ENV).ok().as_deref(), global),
+            file: log_level(std::env::var(LOG_LEVEL_FILE_ENV).ok().as_deref(), global),
+        }
+    });
+    let max_age_days = log_retention_days(std::env::var(LOG_RETENTION_ENV).ok().as_deref());
+    let period = rotation_period(std::env::var(LOG_ROTATION_ENV).ok().as_deref());
+    let max_size = log_max_size(std::env::var(LOG_MAX_SIZE_ENV).ok().as_deref());
+    LOG_COMPRESSION.get_or_init(|| {
+        log_compression(std::env::var(LOG_COMPRESS_ENV).ok().as_deref()).unwrap_or_else(|e| {
+            eprintln!("Log rotation: {e}, using gzip");
+            LogCompression::Gzip
+        })
+    });
+    if !rotation_disabled() {
+        rotate_logs(max_age_days, period);
+        init_runtime_rotation(max_age_days, period, max_size);
+    }
+    if let Some(dir) = log_dir().filter(|dir| dir.is_dir()) {
+        verify_archives(&dir);
+    }
+
+    let widths = ColumnWidths {
+        logger: column_width(std::env::var(LOGGER_WIDTH_ENV).ok().as_deref(), LOGGER_NAME_WIDTH),
+        file: column_width(std::env::var(FILE_WIDTH_ENV).ok().as_deref(), FILE_PATH_WIDTH),
+    };
+
+    let no_color = std::env::var_os(NO_COLOR_ENV);
+    if !stdout_colours_enabled(no_color.as_deref(), io::stdout().is_terminal()) {
+        colored::control::set_override(false);
+    }
+
+    let stdout_filter = move |metadata: &log::Metadata| metadata.level() <= levels.stdout;
+    let file_filter = move |metadata: &log::Metadata| metadata.level() <= levels.file;
+
+    let file_target = if open_log_file(widths) {
+        Target::new(TargetKind::Dispatch(fern::Dispatch::new().chain(fern::Output::call(write_file_record))))
+    } else {
+        Target::new(TargetKind::LogDir {
+            file_name: Some(LOG_FILE_NAME.into()),
+        })
+        .format(move |callback, message, record| format_file(callback, message, record, widths))
+    };
+    let mut targets = vec![
+        Target::new(TargetKind::Stdout)
+            .filter(stdout_filter)
+            .format(move |callback, message, record| format_stdout(callback, message, record, widths)),
+        file_target.filter(file_filter),
+        Target::new(TargetKind::Dispatch(fern::Dispatch::new().chain(fern::Output::call(push_buffer_line))))
+            .filter(stdout_filter)
+            .format(move |callback, message, record| format_buffer(callback, message, record, widths)),
+    ];
+    if std::env::var(LOG_JSON_ENV).as_deref() == Ok("1") {
+        targets.push(
+            Target::new(TargetKind::LogDir {
+                file_name: Some(JSON_LOG_FILE_NAME.into()),
+            })
+            .filter(file_filter)
+            .format(format_json),
+        );
+    }
+    if std::env::var(LOG_JSON_STDERR_ENV).as_deref() == Ok("1") {
+        targets.push(Target::new(TargetKind::Stderr).filter(stdout_filter).format(format_json));
+    }
 
     Builder::new()
         .timezone_strategy(TimezoneStrategy::UseLocal)
-        .max_file_size(1_000_000) // 1 MB, plugin-internal size rotation
-        .level(LevelFilter::Debug)
+        .max_file_size(u128::MAX) // size rotation happens in check_runtime_rotation
+        .level(levels.stdout.max(levels.file))
         .level_for("tao", LevelFilter::Warn)
         .level_for("wry", LevelFilter::Warn)
-        .format(format_log)
-        .targets([
-            Target::new(TargetKind::Stdout),
-            Target::new(TargetKind::LogDir {
-                file_name: Some(LOG_FILE_NAME.into()),
-            }),
-        ])
+        .clear_format()
+        .targets(targets)
         .build()
 }
 
+/// Resolve a log level from the raw value of [`LOG_LEVEL_ENV`] or one of the per-target variables.
+///
+/// Falls back to `default` when the variable is unset or not one of the five level names.
+fn log_level(value: Option<&str>, default: LevelFilter) -> LevelFilter {
+    match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        Some("trace") => LevelFilter::Trace,
+        Some("debug") => LevelFilter::Debug,
+        Some("info") => LevelFilter::Info,
+        Some("warn") => LevelFilter::Warn,
+        Some("error") => LevelFilter::Error,
+        _ => default,
+    }
+}
+
+/// The target levels chosen when the plugin was built, for logging them once the logger is up.
+pub fn levels() -> LogLevels {
+    LOG_LEVELS.get().copied().unwrap_or(LogLevels { stdout: DEFAULT_LOG_LEVEL, file: DEFAULT_LOG_LEVEL })
+}
+
+// ---- In-memory buffer -----------------------------------------------------------
+
+/// Number of formatted log lines kept in memory for the in-app log panel.
+const LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// Event emitted with each new formatted log line.
+pub const LOG_LINE_EVENT: &str = "log-line";
+
+/// The most recent formatted log lines, oldest first, at most [`LOG_BUFFER_CAPACITY`].
+static LOG_BUFFER: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// App handle used to emit [`LOG_LINE_EVENT`]; set once the app is running.
+static LOG_EMITTER: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+/// Start emitting [`LOG_LINE_EVENT`] for every new log line. Lines logged before this are only buffered.
+pub fn attach_emitter(app: tauri::AppHandle) {
+    let _ = LOG_EMITTER.set(app);
+}
+
+/// Formatter for the in-memory buffer target: the plain log line, like [`format_file`] but without driving the
+/// runtime rotation.
+fn format_buffer(
+    callback: fern::FormatCallback,
+    message: &std::fmt::Arguments,
+    record: &log::Record,
+    widths: ColumnWidths,
+) {
+    callback.finish(format_args!("{}", format_line(message, record, false, widths)));
+}
+
+/// Output of the in-memory buffer target: store the formatted line and emit it to the frontend.
+///
+/// The line is rendered before taking the lock, so the lock only covers an O(1) push and pop. The event is sent
+/// from the async runtime, keeping the webview IPC off the logging call.
+fn push_buffer_line(record: &log::Record) {
+    let line = record.args().to_string();
+    push_line(&LOG_BUFFER, line.clone(), LOG_BUFFER_CAPACITY);
+    if let Some(app) = LOG_EMITTER.get() {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = app.emit(LOG_LINE_EVENT, line);
+        });
+    }
+}
+
+/// Append `line` to `buffer`, dropping the oldest lines beyond `capacity`.
+fn push_line(buffer: &Mutex<VecDeque<String>>, line: String, capacity: usize) {
+    let mut buffer = buffer.lock().unwrap_or_else(|e| e.into_inner());
+    while buffer.len() >= capacity {
+        buffer.pop_front();
+    }
+    buffer.push_back(line);
+}
+
+/// Return a copy of the buffered log lines, oldest first.
+pub(crate) fn buffered_lines() -> Vec<String> {
+    LOG_BUFFER.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+}
+
 // ---- Log rotation ---------------------------------------------------------------
 
 /// Rotate log files before the logging plugin opens its file handle.
 ///
 /// Parses the last timestamp from the current log file to decide whether rotation is needed. If the last entry is
-/// from before today, the file gets archived as `project-daystrom_YYYY-MM-DD.log` (using the parsed date, not
-/// filesystem metadata). Empty or missing log files are left alone. Archived logs older than [`MAX_LOG_AGE_DAYS`]
-/// are deleted.
+/// from before the current [`RotationPeriod`], the file gets archived as `project-daystrom_YYYY-MM-DD.log.gz` or as
+/// configured via [`archive_naming`] (using the parsed date of the last entry, not filesystem metadata, so the
+/// age-based cleanup counts from the newest content regardless of the period). Empty or missing log files are left
+/// alone. Archived logs older than `max_age_days` are deleted.
 ///
 /// Errors go to stderr because the logger is not yet initialized.
-fn rotate_logs() {
+fn rotate_logs(max_age_days: i64, period: RotationPeriod) {
     let Some(dir) = log_dir() else { return };
     if !dir.is_dir() {
         return;
     }
-    rotate_logs_in(&dir);
+    rotate_logs_in(&dir, max_age_days, period);
 }
 
 /// Return the platform-specific log directory, if applicable.
@@ -97,10 +311,10 @@ fn rotate_logs() {
 /// - macOS: `~/Library/Logs/{identifier}/`
 /// - Windows: `%LOCALAPPDATA%/{identifier}/logs/`
 /// - Other: `None` (no rotation needed)
-fn log_dir() -> Option<PathBuf> {
+pub(crate) fn log_dir() -> Option<PathBuf> {
     #[cfg(target_os = "macos")]
     {
-        Some(dirs::home_dir()?.join(format!("Library/Logs/{}", env!("TAURI_IDENTIFIER"))))
+        Some(home_dir()?.join(format!("Library/Logs/{}", env!("TAURI_IDENTIFIER"))))
     }
 
     #[cfg(target_os = "windows")]
@@ -114,68 +328,120 @@ fn log_dir() -> Option<PathBuf> {
     }
 }
 
+/// Whether a missing home directory has been reported on stderr, see [`home_dir`].
+#[cfg(not(target_os = "windows"))]
+static HOME_DIR_MISSING_PRINTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether a missing home directory has been reported through the logger, see [`home_dir`].
+#[cfg(not(target_os = "windows"))]
+static HOME_DIR_MISSING_LOGGED: AtomicBool = AtomicBool::new(false);
+
+/// Return the user's home directory, for the log directory and game detection (not needed on Windows).
+///
+/// In a sandboxed or daemon context there may be none, which silently disables both. This is reported once on
+/// stderr while the logger is not up yet (the log directory is resolved before it), and once as a warning as soon as
+/// it is.
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    let home = dirs::home_dir();
+    if home.is_none() {
+        let message = "Could not resolve the home directory; game detection and log rotation are disabled";
+        if log::max_level() == LevelFilter::Off {
+            if !HOME_DIR_MISSING_PRINTED.swap(true, Ordering::Relaxed) {
+                eprintln!("{message}");
+            }
+        } else if !HOME_DIR_MISSING_LOGGED.swap(true, Ordering::Relaxed) {
+            log::warn!(target: "Logging", "{message}");
+        }
+    }
+    home
+}
+
 // ---- Runtime rotation state -----------------------------------------------------
 
-/// Tracks the current date so [`check_runtime_rotation`] can detect midnight crossings.
+/// Tracks the current date so [`check_runtime_rotation`] can detect midnight and period crossings, and the size
+/// limit for its size rotation.
 struct RotationState {
     current_date: time::Date,
     log_dir: PathBuf,
+    max_age_days: i64,
+    period: RotationPeriod,
+    max_size: u64,
 }
 
 /// Global state for runtime log rotation, initialized by [`init_runtime_rotation`].
 static ROTATION_STATE: Mutex<Option<RotationState>> = Mutex::new(None);
 
-/// Initialize the runtime rotation state with today's date and the log directory.
+/// Initialize the runtime rotation state with today's date, the log directory, the retention window, the rotation
+/// period, and the size limit.
 ///
-/// Called once from [`build_plugin`] after the startup rotation has completed. On platforms without a log directory
-/// (non-macOS), this is a no-op.
-fn init_runtime_rotation() {
+/// Called once from [`build_plugin`] after the startup rotation has completed. Creates the log directory on a fresh
+/// install so rotation also works on the first day; if that fails, runtime rotation stays disabled. On platforms
+/// without a log directory, this is a no-op.
+fn init_runtime_rotation(max_age_days: i64, period: RotationPeriod, max_size: u64) {
     let Some(dir) = log_dir() else { return };
+    if !ensure_log_dir(&dir) {
+        return;
+    }
     let today = time::OffsetDateTime::now_local()
         .unwrap_or_else(|_| time::OffsetDateTime::now_utc())
         .date();
     *ROTATION_STATE.lock().unwrap() = Some(RotationState {
         current_date: today,
         log_dir: dir,
+        max_age_days,
+        period,
+        max_size,
     });
 }
 
+/// Create `dir` (and any missing parents) if it does not exist yet.
+///
+/// Returns `false` with a stderr message if the directory cannot be created, because the logger is not yet
+/// initialized.
+fn ensure_log_dir(dir: &Path) -> bool {
+    match fs::create_dir_all(dir) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("Runtime rotation: disabled, cannot create {}: {e}", dir.display());
+            false
+        }
+    }
+}
+
 // ---- Log cleanup & rotation -----------------------------------------------------
 
 /// Core rotation logic, separated from [`rotate_logs`] for testability.
-fn rotate_logs_in(dir: &Path) {
-    let today = time::OffsetDateTime::now_local()
-        .unwrap_or_else(|_| time::OffsetDateTime::now_utc())
-        .date();
-    let date_fmt = time::macros::format_description!("[year]-[month]-[day]");
+fn rotate_logs_in(dir: &Path, max_age_days: i64, period: RotationPeriod) {
+    let now = time::OffsetDateTime::now_local().unwrap_or_else(|_| time::OffsetDateTime::now_utc());
+    let today = now.date();
 
-    // Rotate current log file if its last entry is from before today
+    // Rotate current log file if its last entry is from before the current period
     let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
     if log_file.exists() {
-        match last_log_date(&log_file) {
-            Some(last_date) if last_date < today => {
-                if let Ok(date_str) = last_date.format(&date_fmt) {
-                    let time_suffix = normalize_plugin_archives(dir, &date_str);
-                    let archive_name = match &time_suffix {
-                        Some(ts) => format!("{LOG_FILE_NAME}_{date_str}_{ts}.log"),
-                        None => format!("{LOG_FILE_NAME}_{date_str}.log"),
-                    };
-                    let archive_path = dir.join(&archive_name);
-
-                    if archive_path.exists() {
-                        eprintln!(
-                            "Log rotation: {archive_name} already exists, skipping {}",
-                            log_file.display()
-                        );
-                    } else if let Err(e) = fs::rename(&log_file, &archive_path) {
-                        eprintln!(
-                            "Log rotation: failed to archive {} as {archive_name}: {e}",
-                            log_file.display()
-                        );
-                    }
+        match last_log_date(&log_file, now.offset()) {
+            Some(last_date) if period.start(last_date) < period.start(today) => {
+                let time_suffix = archive_time_suffix(dir, &log_file, last_date, false);
+                let archive_path = archive_naming().archive_path(dir, last_date, time_suffix.as_deref());
+                let archive_name = archive_path.strip_prefix(dir).unwrap_or(&archive_path).display().to_string();
+
+                if archive_exists(&archive_path) {
+                    eprintln!(
+                        "Log rotation: {archive_name} already exists, skipping {}",
+                        log_file.display()
+                    );
+                } else if let Err(e) = create_archive_dir(&archive_path)
+                    .and_then(|()| fs::rename(&log_file, &archive_path))
+                {
+                    eprintln!(
+                        "Log rotation: failed to archive {} as {archive_name}: {e}",
+                        log_file.display()
+                    );
+                } else if let Err(e) = compress_archive(&archive_path) {
+                    eprintln!("Log rotation: failed to compress {archive_name}: {e}");
                 }
             }
-            Some(_) => {} // last entry is from today, nothing to do
+            Some(_) => {} // last entry is from the current period, nothing to do
             None => {
                 // File exists but contains no valid timestamps — truncate it
                 if let Err(e) = fs::write(&log_file, "") {
@@ -188,259 +454,1310 @@ fn rotate_logs_in(dir: &Path) {
         }
     }
 
-    cleanup_old_archives(dir, today);
+    cleanup_old_archives(dir, today, max_age_days);
 }
 
-/// Number of days to keep archived log files.
+/// Default number of days to keep archived log files.
 const MAX_LOG_AGE_DAYS: i64 = 30;
 
-/// Delete archived log files older than [`MAX_LOG_AGE_DAYS`].
+/// Environment variable that overrides [`MAX_LOG_AGE_DAYS`].
+const LOG_RETENTION_ENV: &str = "DAYSTROM_LOG_RETENTION_DAYS";
+
+/// Resolve the archive retention window from the raw [`LOG_RETENTION_ENV`] value.
 ///
-/// Recognizes both our date-only archives (`project-daystrom_YYYY-MM-DD.log`) and the plugin's size-rotation
-/// archives (`project-daystrom_YYYY-MM-DD_HH-MM-SS.log`) by parsing only the first 10 characters after the
-/// prefix as a date.
-fn cleanup_old_archives(dir: &Path, today: time::Date) {
-    let date_fmt = time::macros::format_description!("[year]-[month]-[day]");
-    let prefix = format!("{LOG_FILE_NAME}_");
-    let entries = match fs::read_dir(dir) {
-        Ok(e) => e,
-        Err(e) => {
-            eprintln!("Log rotation: cannot read {}: {e}", dir.display());
-            return;
-        }
-    };
+/// Falls back to [`MAX_LOG_AGE_DAYS`] when the variable is unset or not a non-negative integer.
+fn log_retention_days(value: Option<&str>) -> i64 {
+    value
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .map_or(MAX_LOG_AGE_DAYS, i64::from)
+}
 
-    for entry in entries.flatten() {
-        let file_name = entry.file_name();
-        let name = file_name.to_string_lossy();
+/// Environment variable that selects the [`RotationPeriod`]: `daily` (default), `weekly`, or `monthly`.
+const LOG_ROTATION_ENV: &str = "DAYSTROM_LOG_ROTATION";
 
-        let Some(rest) = name.strip_prefix(prefix.as_str()) else {
-            continue;
-        };
-        if !rest.ends_with(".log") || rest.len() < 10 {
-            continue;
+/// Environment variable that turns off startup and runtime rotation when set to `1`, so the log file grows without
+/// limit. Existing archives are then not cleaned up either.
+const NO_LOG_ROTATION_ENV: &str = "DAYSTROM_NO_LOG_ROTATION";
+
+/// Whether rotation is turned off via [`NO_LOG_ROTATION_ENV`].
+pub fn rotation_disabled() -> bool {
+    std::env::var(NO_LOG_ROTATION_ENV).as_deref() == Ok("1")
+}
+
+/// Environment variable that selects the [`LogCompression`] of new archives: `gzip` (default), `zstd`, or `none`.
+const LOG_COMPRESS_ENV: &str = "DAYSTROM_LOG_COMPRESS";
+
+/// How new archives are compressed.
+///
+/// Defaults to gzip, overriding the `none` default first asked for with this setting: archives have been gzipped
+/// since compression was introduced, and unconfigured installs should not start writing plain archives again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum LogCompression {
+    /// Gzip, giving `.log.gz` archives.
+    #[default]
+    Gzip,
+    /// Zstandard, giving `.log.zst` archives.
+    Zstd,
+    /// No compression, giving plain `.log` archives.
+    None,
+}
+
+impl LogCompression {
+    /// Extension appended to `.log` by this compression, empty for [`LogCompression::None`].
+    fn extension(self) -> &'static str {
+        match self {
+            LogCompression::Gzip => ".gz",
+            LogCompression::Zstd => ".zst",
+            LogCompression::None => "",
         }
-        let Ok(file_date) = time::Date::parse(&rest[..10], &date_fmt) else {
-            continue;
-        };
+    }
+}
+
+/// Compression chosen when the plugin was built; [`LogCompression::Gzip`] until then.
+static LOG_COMPRESSION: OnceLock<LogCompression> = OnceLock::new();
+
+/// Resolve the archive compression from the raw [`LOG_COMPRESS_ENV`] value; unset selects [`LogCompression::Gzip`].
+///
+/// Any other value than `gzip`, `zstd`, or `none` is rejected.
+fn log_compression(value: Option<&str>) -> Result<LogCompression, String> {
+    let Some(value) = value.map(|v| v.trim().to_ascii_lowercase()) else { return Ok(LogCompression::Gzip) };
+    match value.as_str() {
+        "gzip" => Ok(LogCompression::Gzip),
+        "zstd" => Ok(LogCompression::Zstd),
+        "none" => Ok(LogCompression::None),
+        _ => Err(format!("unknown {LOG_COMPRESS_ENV} value {value:?}, use gzip, zstd, or none")),
+    }
+}
+
+/// How much time one log archive covers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RotationPeriod {
+    /// One archive per day.
+    Daily,
+    /// One archive per ISO week (Monday to Sunday).
+    Weekly,
+    /// One archive per calendar month.
+    Monthly,
+}
 
-        if (today - file_date).whole_days() > MAX_LOG_AGE_DAYS {
-            if let Err(e) = fs::remove_file(entry.path()) {
-                eprintln!("Log rotation: failed to delete old log {name}: {e}");
+impl RotationPeriod {
+    /// Return the first day of the period containing `date`.
+    fn start(self, date: time::Date) -> time::Date {
+        match self {
+            RotationPeriod::Daily => date,
+            RotationPeriod::Weekly => {
+                date - time::Duration::days(i64::from(date.weekday().number_days_from_monday()))
             }
+            RotationPeriod::Monthly => date.replace_day(1).unwrap_or(date),
         }
     }
 }
 
-/// Rename plugin-rotated archives so timestamps reflect content start instead of rotation time.
+/// Resolve the rotation period from the raw [`LOG_ROTATION_ENV`] value.
+///
+/// Falls back to [`RotationPeriod::Daily`] when the variable is unset or unrecognized.
+fn rotation_period(value: Option<&str>) -> RotationPeriod {
+    match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        Some("weekly") => RotationPeriod::Weekly,
+        Some("monthly") => RotationPeriod::Monthly,
+        _ => RotationPeriod::Daily,
+    }
+}
+
+/// Resolve the size limit of the current log file in bytes from the raw [`LOG_MAX_SIZE_ENV`] value (kilobytes).
+///
+/// Falls back to [`MAX_LOG_FILE_SIZE`] when the variable is unset, zero, or not an integer.
+fn log_max_size(value: Option<&str>) -> u64 {
+    value
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|&kb| kb > 0)
+        .map_or(MAX_LOG_FILE_SIZE, |kb| kb.saturating_mul(1000))
+}
+
+/// Maximum combined size of all archived log files, enforced after the age-based cleanup.
+const MAX_LOG_TOTAL_BYTES: u64 = 100 * 1024 * 1024;
+
+// ---- Archive naming -------------------------------------------------------------
+
+/// Environment variable with the template for archive file names, without the `.log` extension.
 ///
-/// The plugin's size-based rotation creates files like `{LOG_FILE_NAME}_{date}_HH-MM-SS.log` where HH-MM-SS is
-/// the rotation (end) time. This function renames them so each file's timestamp indicates when its content begins:
-/// - The earliest file gets `_00-00-00` (midnight / start of day)
-/// - Each subsequent file gets the original timestamp of its predecessor
+/// Placeholders: `{name}` ([`LOG_FILE_NAME`]), `{app}` (the app identifier), `{date}` (`YYYY-MM-DD`), `{year}`,
+/// `{month}` (`YYYY-MM`), and `{time}` (`HH-MM-SS`). `{date}` is required, since archives are dated by their name.
+const LOG_ARCHIVE_NAME_ENV: &str = "DAYSTROM_LOG_ARCHIVE_NAME";
+
+/// Environment variable with the template for the archive subfolder, relative to the log directory (e.g. `{month}`).
 ///
-/// Returns the last original timestamp (used as time suffix for the copy-truncate archive), or `None` if no
-/// plugin-rotated files were found (or already normalized).
-fn normalize_plugin_archives(dir: &Path, date_str: &str) -> Option<String> {
-    let prefix = format!("{LOG_FILE_NAME}_{date_str}_");
-    let entries = fs::read_dir(dir).ok()?;
+/// Takes the same placeholders as [`LOG_ARCHIVE_NAME_ENV`] except `{time}`. Unset means no subfolder.
+const LOG_ARCHIVE_DIR_ENV: &str = "DAYSTROM_LOG_ARCHIVE_DIR";
+
+/// Default archive name template: `project-daystrom_YYYY-MM-DD.log`, or `project-daystrom_YYYY-MM-DD_HH-MM-SS.log`
+/// with a time suffix.
+const DEFAULT_ARCHIVE_NAME: &str = "{name}_{date}_{time}";
+
+/// How deep [`list_archives`] descends into subfolders of the log directory, so archives are found whatever
+/// subfolder template they were written with.
+const ARCHIVE_SCAN_DEPTH: usize = 3;
+
+/// Characters that separate `{time}` from the rest of a name; dropped together with `{time}` when there is no time.
+const TIME_SEPARATORS: &[char] = &['_', '-', '.', ' '];
+
+/// A parsed piece of an archive naming template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum NamePart {
+    Literal(String),
+    Name,
+    App,
+    Date,
+    Year,
+    Month,
+    /// The optional time suffix, with the separator in front of it.
+    Time { separator: Option<char> },
+}
+
+/// Templates for archive subfolders and file names, see [`LOG_ARCHIVE_DIR_ENV`] and [`LOG_ARCHIVE_NAME_ENV`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ArchiveNaming {
+    dir: Vec<NamePart>,
+    name: Vec<NamePart>,
+}
 
-    let mut times: Vec<String> = entries
-        .flatten()
-        .filter_map(|entry| {
-            let name = entry.file_name();
-            let name = name.to_string_lossy();
-            let rest = name.strip_prefix(prefix.as_str())?;
-            let time_part = rest.strip_suffix(".log")?;
-            // HH-MM-SS is exactly 8 characters
-            (time_part.len() == 8).then(|| time_part.to_string())
+/// Naming chosen from the environment on first use, see [`archive_naming`].
+static ARCHIVE_NAMING: OnceLock<ArchiveNaming> = OnceLock::new();
+
+/// The archive naming configured via [`LOG_ARCHIVE_DIR_ENV`] and [`LOG_ARCHIVE_NAME_ENV`].
+///
+/// Invalid templates are reported on stderr, because the logger may not be initialized yet, and replaced by the
+/// default flat naming.
+fn archive_naming() -> &'static ArchiveNaming {
+    ARCHIVE_NAMING.get_or_init(|| {
+        let dir = std::env::var(LOG_ARCHIVE_DIR_ENV).ok();
+        let name = std::env::var(LOG_ARCHIVE_NAME_ENV).ok();
+        ArchiveNaming::new(dir.as_deref(), name.as_deref()).unwrap_or_else(|e| {
+            eprintln!("Log rotation: {e}, using the default archive naming");
+            ArchiveNaming::default()
         })
-        .collect();
+    })
+}
 
-    if times.is_empty() {
-        return None;
+impl Default for ArchiveNaming {
+    fn default() -> Self {
+        ArchiveNaming::new(None, None).expect("default archive naming is valid")
     }
+}
+
+impl ArchiveNaming {
+    /// Parse the subfolder and file name templates; `None` selects no subfolder and [`DEFAULT_ARCHIVE_NAME`].
+    ///
+    /// A name template without `{time}` gets `_{time}` appended, so size rotations of the same day stay apart.
+    fn new(dir: Option<&str>, name: Option<&str>) -> Result<Self, String> {
+        let dir_template = dir.map(str::trim).unwrap_or_default();
+        let escapes = Path::new(dir_template).components().any(|c| c.as_os_str() == "..");
+        if Path::new(dir_template).is_absolute() || escapes {
+            return Err(format!("archive folder template {dir_template:?} must stay inside the log directory"));
+        }
+        let dir = parse_name_template(dir_template)?;
+        if dir.iter().any(|part| matches!(part, NamePart::Time { .. })) {
+            return Err(format!("archive folder template {dir_template:?} must not contain {{time}}"));
+        }
 
-    times.sort();
+        let name_template = name.map(str::trim).filter(|n| !n.is_empty()).unwrap_or(DEFAULT_ARCHIVE_NAME);
+        if name_template.contains(['/', '\\']) {
+            return Err(format!("archive name template {name_template:?} must not contain path separators"));
+        }
+        let mut name = parse_name_template(name_template)?;
+        if !name.contains(&NamePart::Date) {
+            return Err(format!("archive name template {name_template:?} must contain {{date}}"));
+        }
+        if !name.iter().any(|part| matches!(part, NamePart::Time { .. })) {
+            name.push(NamePart::Time { separator: Some('_') });
+        }
+        Ok(ArchiveNaming { dir, name })
+    }
 
-    // First file already starts at midnight — archives were already normalized
-    if times[0] == "00-00-00" {
-        return None;
+    /// Path of the plain-text archive for a log whose last entry is from `date`, with an optional time suffix.
+    fn archive_path(&self, log_dir: &Path, date: time::Date, time_suffix: Option<&str>) -> PathBuf {
+        let name = render_name(&self.name, date, time_suffix);
+        log_dir.join(render_name(&self.dir, date, None)).join(format!("{name}.log"))
     }
 
-    // Rename from early to late: each slot is freed before it gets reused
-    let mut prev_time = "00-00-00".to_string();
-    for time in &times {
-        let old_name = format!("{LOG_FILE_NAME}_{date_str}_{time}.log");
-        let new_name = format!("{LOG_FILE_NAME}_{date_str}_{prev_time}.log");
+    /// Parse an archive file name (plain, gzip- or zstd-compressed) into its date and optional time suffix.
+    ///
+    /// Returns `None` for files that do not match the name template, e.g. the current log file.
+    fn parse_file_name(&self, file_name: &str) -> Option<(time::Date, Option<String>)> {
+        let stem = COMPRESSED_EXTENSIONS
+            .iter()
+            .find_map(|ext| file_name.strip_suffix(ext)?.strip_suffix(".log"))
+            .or_else(|| file_name.strip_suffix(".log"))?;
+        let (date, time_suffix) = match_name(&self.name, stem, None, None)?;
+        Some((date?, time_suffix))
+    }
+}
 
-        if let Err(e) = fs::rename(dir.join(&old_name), dir.join(&new_name)) {
-            eprintln!("Log rotation: failed to rename {old_name} to {new_name}: {e}");
+/// Split a template into literals and placeholders.
+fn parse_name_template(template: &str) -> Result<Vec<NamePart>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}').ok_or_else(|| format!("unclosed placeholder in {template:?}"))? + start;
+        let mut literal = rest[..start].to_string();
+        let part = match &rest[start + 1..end] {
+            "name" => NamePart::Name,
+            "app" => NamePart::App,
+            "date" => NamePart::Date,
+            "year" => NamePart::Year,
+            "month" => NamePart::Month,
+            "time" => {
+                let separator = literal.ends_with(TIME_SEPARATORS).then(|| literal.pop()).flatten();
+                NamePart::Time { separator }
+            }
+            other => return Err(format!("unknown placeholder {{{other}}} in {template:?}")),
+        };
+        if !literal.is_empty() {
+            parts.push(NamePart::Literal(literal));
         }
-        prev_time = time.clone();
+        parts.push(part);
+        rest = &rest[end + 1..];
     }
-
-    Some(prev_time)
+    if !rest.is_empty() {
+        parts.push(NamePart::Literal(rest.to_string()));
+    }
+    Ok(parts)
 }
 
-/// Copy-truncate the current log file into a dated archive.
-///
-/// Uses `fs::copy` + `set_len(0)` instead of rename because the logging plugin holds the file handle open. When
-/// `time_suffix` is provided, the archive includes a time component (`_YYYY-MM-DD_HH-MM-SS.log`); otherwise it
-/// uses date-only naming. Skips silently if the log file is missing, has no valid timestamps, or the target archive
-/// already exists.
-fn copy_truncate_rotation(dir: &Path, time_suffix: Option<&str>) {
-    let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
-    if !log_file.exists() {
-        return;
+/// Fill in a parsed template. Without a `time_suffix`, the `{time}` placeholder is dropped along with its separator.
+fn render_name(parts: &[NamePart], date: time::Date, time_suffix: Option<&str>) -> String {
+    let mut out = String::new();
+    for part in parts {
+        match part {
+            NamePart::Literal(text) => out.push_str(text),
+            NamePart::Name => out.push_str(LOG_FILE_NAME),
+            NamePart::App => out.push_str(env!("TAURI_IDENTIFIER")),
+            NamePart::Date => out.push_str(&format!("{:04}-{:02}-{:02}", date.year(), date.month() as u8, date.day())),
+            NamePart::Year => out.push_str(&format!("{:04}", date.year())),
+            NamePart::Month => out.push_str(&format!("{:04}-{:02}", date.year(), date.month() as u8)),
+            NamePart::Time { separator } => {
+                if let Some(suffix) = time_suffix {
+                    out.extend(*separator);
+                    out.push_str(suffix);
+                }
+            }
+        }
     }
+    out
+}
 
-    let Some(last_date) = last_log_date(&log_file) else { return };
-    let date_fmt = time::macros::format_description!("[year]-[month]-[day]");
-    let Ok(date_str) = last_date.format(&date_fmt) else { return };
-    let archive_name = match time_suffix {
-        Some(ts) => format!("{LOG_FILE_NAME}_{date_str}_{ts}.log"),
-        None => format!("{LOG_FILE_NAME}_{date_str}.log"),
+/// Match `text` against a parsed template, returning the `{date}` and `{time}` values found so far and in the rest.
+///
+/// Only `{time}` is optional, so this backtracks at most once per time placeholder.
+fn match_name(
+    parts: &[NamePart],
+    text: &str,
+    date: Option<time::Date>,
+    time_suffix: Option<String>,
+) -> Option<(Option<time::Date>, Option<String>)> {
+    let Some((part, parts)) = parts.split_first() else {
+        return text.is_empty().then_some((date, time_suffix));
     };
-    let archive_path = dir.join(&archive_name);
-
-    if archive_path.exists() {
-        return;
+    match part {
+        NamePart::Literal(literal) => match_name(parts, text.strip_prefix(literal.as_str())?, date, time_suffix),
+        NamePart::Name => match_name(parts, text.strip_prefix(LOG_FILE_NAME)?, date, time_suffix),
+        NamePart::App => match_name(parts, text.strip_prefix(env!("TAURI_IDENTIFIER"))?, date, time_suffix),
+        NamePart::Date => {
+            let day = text.get(..10).and_then(|value| {
+                time::Date::parse(value, time::macros::format_description!("[year]-[month]-[day]")).ok()
+            });
+            let (parsed, len) = match day {
+                Some(parsed) => (parsed, 10),
+                None => (parse_iso_week(text.get(..8)?)?, 8),
+            };
+            match_name(parts, &text[len..], Some(parsed), time_suffix)
+        }
+        NamePart::Year => {
+            text.get(..4)?.bytes().all(|b| b.is_ascii_digit()).then_some(())?;
+            match_name(parts, &text[4..], date, time_suffix)
+        }
+        NamePart::Month => {
+            let month = text.get(..7)?;
+            let valid = month.bytes().enumerate().all(|(i, b)| if i == 4 { b == b'-' } else { b.is_ascii_digit() });
+            valid.then_some(())?;
+            match_name(parts, &text[7..], date, time_suffix)
+        }
+        NamePart::Time { separator } => {
+            let with_time = separator
+                .map_or(Some(text), |sep| text.strip_prefix(sep))
+                .and_then(|rest| {
+                    let value = rest.get(..8)?;
+                    time::Time::parse(value, time::macros::format_description!("[hour]-[minute]-[second]")).ok()?;
+                    match_name(parts, &rest[8..], date, Some(value.to_string()))
+                });
+            with_time.or_else(|| match_name(parts, text, date, time_suffix))
+        }
     }
+}
 
-    if let Err(e) = fs::copy(&log_file, &archive_path) {
-        eprintln!("Runtime rotation: failed to copy log to {archive_name}: {e}");
-        return;
+/// Parse an ISO week (`YYYY-Www`, e.g. `2026-W08`) in place of a `{date}` into the week's Sunday.
+///
+/// Taking the last day of the week means a weekly archive is only aged out once all of its entries are old enough.
+fn parse_iso_week(text: &str) -> Option<time::Date> {
+    let (year, week) = text.split_once("-W")?;
+    if year.len() != 4 || week.len() != 2 || !format!("{year}{week}").bytes().all(|b| b.is_ascii_digit()) {
+        return None;
     }
+    time::Date::from_iso_week_date(year.parse().ok()?, week.parse().ok()?, time::Weekday::Sunday).ok()
+}
 
-    if let Err(e) = fs::File::options()
-        .write(true)
-        .open(&log_file)
-        .and_then(|f| f.set_len(0))
-    {
-        eprintln!("Runtime rotation: failed to truncate {}: {e}", log_file.display());
-    }
+/// An archived log file found by [`list_archives`].
+struct Archive {
+    /// Date parsed from the file name.
+    date: time::Date,
+    /// Time suffix (`HH-MM-SS`) parsed from the file name, if any.
+    time_suffix: Option<String>,
+    /// File name, used as tie-breaker for archives of the same day.
+    name: String,
+    path: PathBuf,
 }
 
-/// Check whether the date has changed since the last log event and rotate if needed.
+/// List all archived log files in `dir` and its subfolders, oldest first.
 ///
-/// Called at the start of every [`format_log`] invocation. The fast path (same date) is a single mutex lock + date
-/// comparison. On date change, performs a copy-truncate rotation followed by archive cleanup.
-fn check_runtime_rotation() {
-    let mut guard = match ROTATION_STATE.lock() {
-        Ok(g) => g,
-        Err(_) => return, // poisoned mutex, skip rotation
-    };
-    let Some(state) = guard.as_mut() else { return };
+/// Recognizes the file names produced by the configured [`archive_naming`], with or without a time suffix, plain or
+/// gzip-compressed. By default these are `project-daystrom_YYYY-MM-DD.log` and
+/// `project-daystrom_YYYY-MM-DD_HH-MM-SS.log`. An ISO week (`YYYY-Www`) is accepted in place of the date, see
+/// [`parse_iso_week`].
+fn list_archives(dir: &Path) -> io::Result<Vec<Archive>> {
+    list_archives_with(dir, archive_naming())
+}
 
-    let today = time::OffsetDateTime::now_local()
-        .unwrap_or_else(|_| time::OffsetDateTime::now_utc())
-        .date();
+/// Core of [`list_archives`] for a given naming, separated for testability.
+fn list_archives_with(dir: &Path, naming: &ArchiveNaming) -> io::Result<Vec<Archive>> {
+    let mut archives = Vec::new();
+    collect_archives(dir, naming, ARCHIVE_SCAN_DEPTH, &mut archives)?;
+    archives.sort_by(|a, b| {
+        a.date.cmp(&b.date).then_with(|| a.time_suffix.cmp(&b.time_suffix)).then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(archives)
+}
 
-    // Fast path: same date, nothing to do
-    if today == state.current_date {
-        return;
+/// Add the archives in `dir` to `archives`, descending up to `depth` levels of subfolders.
+///
+/// Only errors reading `dir` itself are returned; unreadable subfolders are skipped.
+fn collect_archives(dir: &Path, naming: &ArchiveNaming, depth: usize, archives: &mut Vec<Archive>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            if depth > 0 {
+                let _ = collect_archives(&path, naming, depth - 1, archives);
+            }
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if let Some((date, time_suffix)) = naming.parse_file_name(&name) {
+            archives.push(Archive { date, time_suffix, name, path });
+        }
     }
-
-    let date_fmt = time::macros::format_description!("[year]-[month]-[day]");
-    let last_time = state.current_date.format(&date_fmt).ok().and_then(|date_str| {
-        normalize_plugin_archives(&state.log_dir, &date_str)
-    });
-    copy_truncate_rotation(&state.log_dir, last_time.as_deref());
-    cleanup_old_archives(&state.log_dir, today);
-    state.current_date = today;
+    Ok(())
 }
 
-/// Maximum number of bytes to read from the end of a log file when looking for the last timestamp.
-const TAIL_READ_SIZE: u64 = 4096;
+/// Delete an archive, and its folder if that is a now empty subfolder of `log_dir`.
+fn remove_archive(log_dir: &Path, archive: &Archive) -> io::Result<()> {
+    fs::remove_file(&archive.path)?;
+    if let Some(parent) = archive.path.parent().filter(|parent| *parent != log_dir) {
+        let _ = fs::remove_dir(parent); // fails while other archives remain
+    }
+    Ok(())
+}
 
-/// Extract the date from the last timestamped line in a log file.
+/// Delete archived log files older than `max_age_days`, then enforce [`MAX_LOG_TOTAL_BYTES`].
 ///
-/// Reads only the last [`TAIL_READ_SIZE`] bytes to avoid loading large files into memory. Scans backwards through
-/// those lines looking for one starting with an ISO 8601 date (`YYYY-MM-DD`). Returns the parsed date, or `None`
-/// if the file is empty, missing, or contains no valid timestamp.
-fn last_log_date(path: &Path) -> Option<time::Date> {
-    use std::io::{Read, Seek, SeekFrom};
+/// The current log file and archives dated today (or later) are never deleted, whatever the retention window, so a
+/// naming that happens to match the live file cannot remove it.
+fn cleanup_old_archives(dir: &Path, today: time::Date, max_age_days: i64) {
+    let archives = match list_archives(dir) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Log rotation: cannot read {}: {e}", dir.display());
+            return;
+        }
+    };
 
-    let mut file = fs::File::open(path).ok()?;
-    let len = file.metadata().ok()?.len();
-    if len == 0 {
-        return None;
+    let live_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+    for archive in archives {
+        if archive.path == live_file || archive.date >= today {
+            continue;
+        }
+        if (today - archive.date).whole_days() > max_age_days {
+            if let Err(e) = remove_archive(dir, &archive) {
+                eprintln!("Log rotation: failed to delete old log {}: {e}", archive.name);
+            }
+        }
     }
 
-    let read_from = len.saturating_sub(TAIL_READ_SIZE);
-    file.seek(SeekFrom::Start(read_from)).ok()?;
+    cleanup_oversized_archives(dir, MAX_LOG_TOTAL_BYTES);
+}
 
-    let mut buf = String::new();
-    file.read_to_string(&mut buf).ok()?;
+/// Delete the oldest archived log files until their combined size is at most `max_total_bytes`.
+///
+/// Each deletion is reported on stderr, since this usually means a very chatty day rather than normal retention.
+fn cleanup_oversized_archives(dir: &Path, max_total_bytes: u64) {
+    let Ok(archives) = list_archives(dir) else { return };
+    let sized: Vec<_> = archives
+        .into_iter()
+        .filter_map(|archive| Some((fs::metadata(&archive.path).ok()?.len(), archive)))
+        .collect();
 
-    let date_fmt = time::macros::format_description!("[year]-[month]-[day]");
+    let mut total: u64 = sized.iter().map(|(size, _)| size).sum();
+    for (size, archive) in &sized {
+        if total <= max_total_bytes {
+            break;
+        }
+        match remove_archive(dir, archive) {
+            Ok(()) => {
+                total -= size;
+                eprintln!(
+                    "Log rotation: deleted {} ({size} bytes) to stay under the {max_total_bytes} byte archive limit",
+                    archive.name
+                );
+            }
+            Err(e) => eprintln!("Log rotation: failed to delete oversized log {}: {e}", archive.name),
+        }
+    }
+}
 
-    // If we seeked into the middle of a line, the first "line" is a fragment — skip it
-    let lines = if read_from > 0 {
-        buf.split_once('\n').map_or("", |(_fragment, rest)| rest)
-    } else {
-        &buf
+/// Find archives in `dir` that claim the same date and time slot, e.g. after a crash mid-rename or mid-compression.
+///
+/// Each collision is reported on stderr because the logger is not yet initialized. An interrupted compression (a plain
+/// archive next to a complete `.gz` or `.zst` of the same content) is repaired by removing the plain copy. Returns
+/// the slots that still collide, each as the colliding paths relative to `dir`.
+fn verify_archives(dir: &Path) -> Vec<Vec<String>> {
+    let archives = match list_archives(dir) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Log rotation: cannot read {}: {e}", dir.display());
+            return Vec::new();
+        }
     };
 
-    lines.lines().rev().find_map(|line| {
-        let date_str = line.get(..10)?;
-        time::Date::parse(date_str, &date_fmt).ok()
-    })
+    let mut collisions = Vec::new();
+    for slot in archives.chunk_by(|a, b| a.date == b.date && a.time_suffix == b.time_suffix) {
+        if let [plain, compressed] = slot {
+            if is_interrupted_compression(plain, compressed) {
+                match fs::remove_file(&plain.path) {
+                    Ok(()) => {
+                        eprintln!("Log rotation: removed {} left behind by an interrupted compression", plain.name);
+                        continue;
+                    }
+                    Err(e) => eprintln!("Log rotation: failed to delete duplicate log {}: {e}", plain.name),
+                }
+            }
+        }
+        if slot.len() > 1 {
+            let names: Vec<String> = slot
+                .iter()
+                .map(|archive| archive.path.strip_prefix(dir).unwrap_or(&archive.path).display().to_string())
+                .collect();
+            eprintln!("Log rotation: archives claim the same slot: {}", names.join(", "));
+            collisions.push(names);
+        }
+    }
+    collisions
 }
 
-// ---- Log formatting -------------------------------------------------------------
+/// Whether `compressed` is the complete compressed copy of `plain`, left next to it by an interrupted
+/// [`compress_archive`].
+fn is_interrupted_compression(plain: &Archive, compressed: &Archive) -> bool {
+    let counterpart = [LogCompression::Gzip, LogCompression::Zstd]
+        .into_iter()
+        .any(|compression| compressed.path == compressed_path(&plain.path, compression));
+    counterpart
+        && read_archive(&compressed.path)
+            .is_ok_and(|unpacked| fs::read(&plain.path).is_ok_and(|content| content == unpacked))
+}
 
-/// Unit Separator, used as delimiter between logger name and message from the JS frontend.
-const SEP: char = '\x1F';
+/// Return the time suffix (`HH-MM-SS`) for archiving `log_file`: the time of its first entry, when its content
+/// starts.
+///
+/// Size rotations always get a suffix. A period rotation only gets one if `date` already has archives with a
+/// time suffix, so the archives of a day sort in content order; otherwise it is archived under the date alone.
+/// Returns `None` if no suffix is needed or the file does not start with a timestamp.
+fn archive_time_suffix(dir: &Path, log_file: &Path, date: time::Date, size_rotation: bool) -> Option<String> {
+    if !size_rotation {
+        let archives = list_archives(dir).ok()?;
+        if !archives.iter().any(|archive| archive.date == date && archive.time_suffix.is_some()) {
+            return None;
+        }
+    }
+    first_log_time(log_file)
+}
 
-/// Display width for the logger name in log output. Matches bit-log's default. Names are right-padded or
-/// left-truncated to this width.
-const LOGGER_NAME_WIDTH: usize = 20;
+/// Read the time (`HH-MM-SS`) of the first entry of a log file, as written in its timestamp.
+fn first_log_time(path: &Path) -> Option<String> {
+    use std::io::Read;
 
-/// Display width for the file path in log output. Paths longer than this are middle-truncated with "...".
-const FILE_PATH_WIDTH: usize = 30;
+    let mut head = String::new();
+    fs::File::open(path).ok()?.take(TIMESTAMP_LEN as u64).read_to_string(&mut head).ok()?;
+    let timestamp = time::OffsetDateTime::parse(&head, TIMESTAMP_FORMAT).ok()?;
+    timestamp.format(time::macros::format_description!("[hour]-[minute]-[second]")).ok()
+}
 
-/// Build the log line matching bit-log's format: `{timestamp} {LEVEL} [{loggerName}] ({file}:{line}): {message}`
+/// Truncate a log file that has grown beyond `max_size` bytes, without archiving it.
 ///
-/// For JS-originated logs, the logger name is embedded in the message as `name\x1Fmessage`. For Rust-originated
-/// logs, `record.target()` is used as the logger name.
-fn format_log(
-    callback: fern::FormatCallback,
-    message: &std::fmt::Arguments,
-    record: &log::Record,
-) {
+/// Used for the JSON Lines log, which has no archives of its own.
+fn truncate_oversized(path: &Path, max_size: u64) {
+    if !fs::metadata(path).is_ok_and(|meta| meta.len() > max_size) {
+        return;
+    }
+    if let Err(e) = fs::File::options().write(true).open(path).and_then(|f| f.set_len(0)) {
+        eprintln!("Runtime rotation: failed to truncate {}: {e}", path.display());
+    }
+}
+
+/// Create the subfolder an archive goes into, if the naming uses one.
+fn create_archive_dir(archive: &Path) -> io::Result<()> {
+    match archive.parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+/// Return the path of the compressed counterpart of an archive (e.g. `*.log` → `*.log.gz`).
+fn compressed_path(archive: &Path, compression: LogCompression) -> PathBuf {
+    let mut name = OsString::from(archive.as_os_str());
+    name.push(compression.extension());
+    PathBuf::from(name)
+}
+
+/// Extensions appended to `.log` by the compressions archives may have been written with.
+const COMPRESSED_EXTENSIONS: [&str; 2] = [".gz", ".zst"];
+
+/// Read a log file or archive, decompressing `.gz` and `.zst` archives by their extension.
+pub(crate) fn read_archive(path: &Path) -> io::Result<Vec<u8>> {
+    let file = fs::File::open(path)?;
+    let mut bytes = Vec::new();
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => GzDecoder::new(file).read_to_end(&mut bytes)?,
+        Some("zst") => zstd::Decoder::new(file)?.read_to_end(&mut bytes)?,
+        _ => io::BufReader::new(file).read_to_end(&mut bytes)?,
+    };
+    Ok(bytes)
+}
+
+/// Check whether an archive exists in its plain or any compressed form.
+fn archive_exists(archive: &Path) -> bool {
+    archive.exists()
+        || COMPRESSED_EXTENSIONS.iter().any(|ext| {
+            let mut name = OsString::from(archive.as_os_str());
+            name.push(ext);
+            Path::new(&name).exists()
+        })
+}
+
+/// Compress a plain-text archive next to itself as configured via [`LOG_COMPRESS_ENV`], see [`compress_archive_with`].
+fn compress_archive(archive: &Path) -> io::Result<PathBuf> {
+    compress_archive_with(archive, LOG_COMPRESSION.get().copied().unwrap_or_default())
+}
+
+/// Compress a plain-text archive next to itself and remove the original, returning the path of the result.
+///
+/// With [`LogCompression::None`] the archive is left as is. On failure the partially written compressed file is
+/// removed, so the plain archive stays the single source of truth.
+fn compress_archive_with(archive: &Path, compression: LogCompression) -> io::Result<PathBuf> {
+    if compression == LogCompression::None {
+        return Ok(archive.to_path_buf());
+    }
+    let target = compressed_path(archive, compression);
+    let result = fs::File::open(archive).and_then(|mut input| {
+        let output = fs::File::create(&target)?;
+        if compression == LogCompression::Zstd {
+            return zstd::stream::copy_encode(input, output, zstd::DEFAULT_COMPRESSION_LEVEL);
+        }
+        let mut encoder = GzEncoder::new(output, Compression::default());
+        io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?;
+        Ok(())
+    });
+    match result {
+        Ok(()) => fs::remove_file(archive).map(|()| target),
+        Err(e) => {
+            let _ = fs::remove_file(&target);
+            Err(e)
+        }
+    }
+}
+
+/// Copy-truncate the current log file into a dated archive, gzip-compressed unless configured otherwise.
+///
+/// Uses `fs::copy` + `set_len(0)` instead of rename because the logging plugin holds the file handle open. The archive
+/// is named by [`archive_naming`] and gets a time component (`_YYYY-MM-DD_HH-MM-SS.log.gz` by default) as decided by
+/// [`archive_time_suffix`], otherwise date-only naming. Skips silently if the log file is missing, has no valid
+/// timestamps, or the target archive already exists.
+/// Returns the path of the written archive, or `None` if it was skipped.
+fn copy_truncate_rotation(dir: &Path, size_rotation: bool) -> Option<PathBuf> {
+    let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+    if !log_file.exists() {
+        return None;
+    }
+
+    let offset = time::OffsetDateTime::now_local()
+        .unwrap_or_else(|_| time::OffsetDateTime::now_utc())
+        .offset();
+    let last_date = last_log_date(&log_file, offset)?;
+    let time_suffix = archive_time_suffix(dir, &log_file, last_date, size_rotation);
+    let archive_path = archive_naming().archive_path(dir, last_date, time_suffix.as_deref());
+    let archive_name = archive_path.strip_prefix(dir).unwrap_or(&archive_path).display().to_string();
+
+    if archive_exists(&archive_path) {
+        return None;
+    }
+
+    copy_truncate(&log_file, &archive_path)
+        .map_err(|e| eprintln!("Runtime rotation: failed to copy log to {archive_name}: {e}"))
+        .ok()
+}
+
+/// Copy `log_file` to `archive_path`, truncate it, and compress the archive.
+///
+/// Only a failed copy is an error, since the log is left untouched then. A failed truncation or compression is
+/// reported on stderr, and the uncompressed archive path returned in the latter case.
+fn copy_truncate(log_file: &Path, archive_path: &Path) -> io::Result<PathBuf> {
+    create_archive_dir(archive_path).and_then(|()| fs::copy(log_file, archive_path))?;
+
+    if let Err(e) = fs::File::options()
+        .write(true)
+        .open(log_file)
+        .and_then(|f| f.set_len(0))
+    {
+        eprintln!("Runtime rotation: failed to truncate {}: {e}", log_file.display());
+    }
+
+    match compress_archive(archive_path) {
+        Ok(compressed) => Ok(compressed),
+        Err(e) => {
+            eprintln!("Runtime rotation: failed to compress {}: {e}", archive_path.display());
+            Ok(archive_path.to_path_buf())
+        }
+    }
+}
+
+/// Check whether the date has changed or the log file has grown too large since the last log event, and rotate if
+/// needed.
+///
+/// Called at the start of every [`format_file`] invocation, so it costs a mutex lock, a date comparison, and a
+/// metadata lookup per log line. When the [`RotationPeriod`] changes, or the log file exceeds the size limit,
+/// performs a copy-truncate rotation followed by archive cleanup. The JSON Lines log is truncated once it exceeds the
+/// same limit.
+fn check_runtime_rotation() {
+    let mut guard = match ROTATION_STATE.lock() {
+        Ok(g) => g,
+        Err(_) => return, // poisoned mutex, skip rotation
+    };
+    let Some(state) = guard.as_mut() else { return };
+
+    let today = time::OffsetDateTime::now_local()
+        .unwrap_or_else(|_| time::OffsetDateTime::now_utc())
+        .date();
+
+    // New period: archive the current file, the size check is moot afterwards
+    if today != state.current_date {
+        let new_period = state.period.start(today) != state.period.start(state.current_date);
+        state.current_date = today;
+        if new_period {
+            copy_truncate_rotation(&state.log_dir, false);
+            cleanup_old_archives(&state.log_dir, today, state.max_age_days);
+            return;
+        }
+    }
+
+    let log_file = state.log_dir.join(format!("{LOG_FILE_NAME}.log"));
+    if fs::metadata(&log_file).is_ok_and(|meta| meta.len() > state.max_size) {
+        copy_truncate_rotation(&state.log_dir, true);
+        cleanup_old_archives(&state.log_dir, today, state.max_age_days);
+    }
+    truncate_oversized(&state.log_dir.join(format!("{JSON_LOG_FILE_NAME}.log")), state.max_size);
+}
+
+/// Archive the current log file and optionally delete all older archives, for a fresh start before reproducing a
+/// bug.
+///
+/// Holds the [`ROTATION_STATE`] lock throughout, so a concurrent [`check_runtime_rotation`] cannot rotate the same
+/// file. Returns the number of files archived or deleted; the archive written by this call is never deleted.
+pub(crate) fn clear_logs(delete_archives: bool) -> Result<usize, String> {
+    let dir = log_dir().ok_or("No log directory on this platform")?;
+    let guard = ROTATION_STATE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    let result = clear_logs_in(&dir, delete_archives);
+    drop(guard); // logging takes the lock, see shutdown()
+    result
+}
+
+/// Core of [`clear_logs`], separated for testability. The caller holds the [`ROTATION_STATE`] lock.
+fn clear_logs_in(dir: &Path, delete_archives: bool) -> Result<usize, String> {
+    let archived = copy_truncate_rotation(dir, true);
+    let mut affected = usize::from(archived.is_some());
+    if !delete_archives {
+        return Ok(affected);
+    }
+
+    let archives = list_archives(dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))?;
+    let mut failed = Vec::new();
+    for archive in archives.into_iter().filter(|a| Some(&a.path) != archived.as_ref()) {
+        match remove_archive(dir, &archive) {
+            Ok(()) => affected += 1,
+            Err(e) => failed.push(format!("{}: {e}", archive.name)),
+        }
+    }
+    if !failed.is_empty() {
+        return Err(format!("Failed to delete {} log archive(s): {}", failed.len(), failed.join("; ")));
+    }
+    Ok(affected)
+}
+
+/// Archive the current log file right away, so the log starts fresh from now on.
+///
+/// Holds the [`ROTATION_STATE`] lock throughout, so a concurrent [`check_runtime_rotation`] cannot rotate the same
+/// file, and resets its date to today. Returns the archive's file name relative to the log directory, or `None` if
+/// there was nothing to archive. See [`rotate_now_in`] for the errors.
+pub(crate) fn rotate_now() -> Result<Option<String>, String> {
+    let dir = log_dir().ok_or("No log directory on this platform")?;
+    let mut guard = ROTATION_STATE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    let now = time::OffsetDateTime::now_local().unwrap_or_else(|_| time::OffsetDateTime::now_utc());
+    let archive = rotate_now_in(&dir, now);
+    if let Some(state) = guard.as_mut() {
+        state.current_date = now.date();
+    }
+    drop(guard); // logging takes the lock, see shutdown()
+    archive
+}
+
+/// Core of [`rotate_now`], separated for testability. The caller holds the [`ROTATION_STATE`] lock.
+///
+/// Unlike [`copy_truncate_rotation`], the archive's time suffix is the time of `now`, since the rotation happens
+/// then rather than when the content started. A missing or empty log file yields `None`. A log file that does not start
+/// with a timestamp, an archive already occupying the slot (two rotations within a second), or a failed copy is an
+/// error.
+fn rotate_now_in(dir: &Path, now: time::OffsetDateTime) -> Result<Option<String>, String> {
+    let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+    if !fs::metadata(&log_file).is_ok_and(|meta| meta.len() > 0) {
+        return Ok(None);
+    }
+    if first_log_time(&log_file).is_none() {
+        return Err(format!("{} does not start with a timestamp, not archiving it", log_file.display()));
+    }
+
+    let last_date = last_log_date(&log_file, now.offset())
+        .ok_or_else(|| format!("{} contains no valid timestamp", log_file.display()))?;
+    let time_suffix = now
+        .format(time::macros::format_description!("[hour]-[minute]-[second]"))
+        .map_err(|e| format!("Failed to format the archive time: {e}"))?;
+    let archive_path = archive_naming().archive_path(dir, last_date, Some(&time_suffix));
+    let archive_name = archive_path.strip_prefix(dir).unwrap_or(&archive_path).display().to_string();
+
+    if archive_exists(&archive_path) {
+        return Err(format!("Archive {archive_name} already exists, try again in a second"));
+    }
+    let archive = copy_truncate(&log_file, &archive_path)
+        .map_err(|e| format!("Failed to copy the log to {archive_name}: {e}"))?;
+    Ok(Some(archive.strip_prefix(dir).unwrap_or(&archive).display().to_string()))
+}
+
+/// Close the log for this session: write a pending repeat summary, run a final rotation check, and flush all
+/// targets.
+///
+/// Called once on app exit, after the last log line. Must not be called from within a log call, since it takes the
+/// [`LOG_FILE`] lock, and [`check_runtime_rotation`] the [`ROTATION_STATE`] lock, that the file target holds there.
+pub fn shutdown() {
+    if let Some(log_file) = LOG_FILE.lock().unwrap_or_else(std::sync::PoisonError::into_inner).as_mut() {
+        log_file.write_summary();
+    }
     check_runtime_rotation();
+    log::logger().flush();
+}
+
+// ---- Log file -------------------------------------------------------------------
+
+/// Collapses identical consecutive log lines into the first one plus a `(repeated N times)` summary, like syslog.
+///
+/// Lines are compared without their timestamp. The summary is written once a different line arrives (or on
+/// [`shutdown`]) and carries the timestamp of the last repeat.
+#[derive(Debug, Default)]
+struct RepeatCollapser {
+    /// The last written line without its timestamp.
+    last_body: Option<String>,
+    /// How often that line was repeated since it was written.
+    repeats: usize,
+    /// Timestamp of the last repeat.
+    last_timestamp: String,
+}
+
+impl RepeatCollapser {
+    /// Count `body` as a repeat if it equals the last written line, returning whether it did.
+    fn repeat(&mut self, timestamp: &str, body: &str) -> bool {
+        if self.last_body.as_deref() != Some(body) {
+            return false;
+        }
+        self.repeats += 1;
+        timestamp.clone_into(&mut self.last_timestamp);
+        true
+    }
+
+    /// Take the summary line for the pending repeats, if there are any.
+    fn summary(&mut self) -> Option<String> {
+        if self.repeats == 0 {
+            return None;
+        }
+        let body = self.last_body.as_deref().unwrap_or_default();
+        let times = if self.repeats == 1 { "time" } else { "times" };
+        let line = format!("{} {body} (repeated {} {times})", self.last_timestamp, self.repeats);
+        self.repeats = 0;
+        Some(line)
+    }
 
+    /// Remember `body` as the last written line.
+    fn written(&mut self, body: String) {
+        self.last_body = Some(body);
+        self.repeats = 0;
+    }
+}
+
+/// The log file opened by [`open_log_file`], with the state for collapsing repeated lines.
+struct LogFile {
+    file: fs::File,
+    widths: ColumnWidths,
+    repeats: RepeatCollapser,
+}
+
+impl LogFile {
+    /// Append a line; write errors are ignored, as there is nowhere left to report them.
+    fn write_line(&mut self, line: &str) {
+        let _ = writeln!(self.file, "{line}");
+    }
+
+    /// Write the summary of pending repeats, if any.
+    fn write_summary(&mut self) {
+        if let Some(summary) = self.repeats.summary() {
+            self.write_line(&summary);
+        }
+    }
+}
+
+/// The log file written by [`write_file_record`]; `None` until [`open_log_file`] succeeds.
+static LOG_FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+/// Open the current log file in the [`log_dir`] for appending, so [`write_file_record`] can write it.
+///
+/// Append mode keeps writes at the end after a copy-truncate rotation. Returns `false` if there is no log directory
+/// or the file cannot be opened.
+fn open_log_file(widths: ColumnWidths) -> bool {
+    let Some(dir) = log_dir() else { return false };
+    let path = dir.join(format!("{LOG_FILE_NAME}.log"));
+    let opened = fs::create_dir_all(&dir).and_then(|()| fs::File::options().create(true).append(true).open(&path));
+    match opened {
+        Ok(file) => {
+            let log_file = LogFile { file, widths, repeats: RepeatCollapser::default() };
+            *LOG_FILE.lock().unwrap_or_else(std::sync::PoisonError::into_inner) = Some(log_file);
+            true
+        }
+        Err(e) => {
+            eprintln!("Logging: cannot open {}: {e}", path.display());
+            false
+        }
+    }
+}
+
+/// Write a record to the log file, collapsing immediate repeats (see [`RepeatCollapser`]).
+///
+/// A repeat costs formatting the line and a comparison, but no write. Otherwise, pending repeats are summarized
+/// before [`check_runtime_rotation`], so the summary stays in the file its repeats belong to.
+fn write_file_record(record: &log::Record) {
+    let mut guard = LOG_FILE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    let Some(log_file) = guard.as_mut() else { return };
     let timestamp = format_timestamp();
-    let level = coloured_level(record.level());
+    let body = format_body(record.args(), record, false, log_file.widths);
+    if log_file.repeats.repeat(&timestamp, &body) {
+        return;
+    }
+
+    log_file.write_summary();
+    check_runtime_rotation();
+    log_file.write_line(&format!("{timestamp} {body}"));
+    log_file.repeats.written(body);
+}
+
+/// Maximum number of bytes to read from the end of a log file when looking for the last timestamp.
+const TAIL_READ_SIZE: u64 = 4096;
+
+/// Read up to `max_bytes` from the end of a file, returning only complete lines.
+///
+/// If the read starts in the middle of the file, the first (partial) line is a fragment and gets skipped. Invalid
+/// UTF-8 (e.g. a multibyte character cut at the window start) is replaced rather than failing the read. Returns the
+/// text and whether the whole file was read.
+fn read_tail(path: &Path, max_bytes: u64) -> io::Result<(String, bool)> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let read_from = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(read_from))?;
+
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    let text = String::from_utf8_lossy(&buf);
+
+    // If we seeked into the middle of a line, the first "line" is a fragment — skip it
+    let lines = if read_from > 0 {
+        text.split_once('\n').map_or("", |(_fragment, rest)| rest)
+    } else {
+        &text
+    };
+    Ok((lines.to_string(), read_from == 0))
+}
+
+/// Format of the timestamp at the start of each log line, as written by [`format_timestamp`].
+const TIMESTAMP_FORMAT: &[time::format_description::BorrowedFormatItem<'static>] = time::macros::format_description!(
+    "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3][offset_hour sign:mandatory]:[offset_minute]"
+);
+
+/// Length of the timestamp at the start of each log line, see [`format_timestamp`].
+const TIMESTAMP_LEN: usize = "2026-02-20T14:30:45.123+01:00".len();
+
+/// Extract the local date of the last timestamped line in a log file.
+///
+/// Starts with the last [`TAIL_READ_SIZE`] bytes to avoid loading large files into memory, and doubles the window
+/// up to [`MAX_LOG_FILE_SIZE`] while it holds no timestamp, so a long stack trace or message after the last entry
+/// does not hide it. Scans backwards through those lines looking for one starting with a full ISO 8601 timestamp
+/// including its offset, and returns that
+/// moment's date at `offset` (the current local offset). An entry written at 23:59 before a DST change, or in
+/// another timezone, thus falls on the day it was written in local time, not on the date printed in the line.
+/// Returns `None` if the file is empty, missing, or contains no valid timestamp.
+fn last_log_date(path: &Path, offset: time::UtcOffset) -> Option<time::Date> {
+    let mut window = TAIL_READ_SIZE;
+    loop {
+        let (lines, whole_file) = read_tail(path, window).ok()?;
+        let date = lines.lines().rev().find_map(|line| {
+            let timestamp = time::OffsetDateTime::parse(line.get(..TIMESTAMP_LEN)?, TIMESTAMP_FORMAT).ok()?;
+            Some(timestamp.to_offset(offset).date())
+        });
+        if date.is_some() || whole_file || window >= MAX_LOG_FILE_SIZE {
+            return date;
+        }
+        window = (window * 2).min(MAX_LOG_FILE_SIZE);
+    }
+}
+
+/// Return the last `n` complete lines of a file, oldest first.
+///
+/// Starts with a [`TAIL_READ_SIZE`] window and doubles it until enough lines are found or the whole file was read,
+/// so large log files are never loaded completely just to show a few lines.
+fn tail_lines(path: &Path, n: usize) -> io::Result<Vec<String>> {
+    let mut window = TAIL_READ_SIZE;
+    loop {
+        let (text, whole_file) = read_tail(path, window)?;
+        let lines: Vec<&str> = text.lines().collect();
+        if lines.len() >= n || whole_file {
+            return Ok(lines[lines.len().saturating_sub(n)..].iter().map(|l| l.to_string()).collect());
+        }
+        window = window.saturating_mul(2);
+    }
+}
+
+/// Return the last `n` lines of the current log file, oldest first.
+///
+/// Returns an empty list if the log file does not exist yet, and an error if the platform has no log directory or
+/// the file cannot be read.
+pub(crate) fn tail_log(n: usize) -> Result<Vec<String>, String> {
+    let dir = log_dir().ok_or("No log directory on this platform")?;
+    let path = dir.join(format!("{LOG_FILE_NAME}.log"));
+    match tail_lines(&path, n) {
+        Ok(lines) => Ok(lines),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+    }
+}
+
+/// Return the current log file followed by up to `archives` of the most recent archives, newest first.
+///
+/// Missing files are left out; an empty list means there is no log directory or nothing has been logged yet.
+pub(crate) fn recent_log_files(archives: usize) -> Vec<PathBuf> {
+    let Some(dir) = log_dir() else { return Vec::new() };
+    let mut files: Vec<PathBuf> = Some(dir.join(format!("{LOG_FILE_NAME}.log"))).into_iter()
+        .filter(|path| path.is_file())
+        .collect();
+    if let Ok(list) = list_archives(&dir) {
+        files.extend(list.into_iter().rev().take(archives).map(|archive| archive.path));
+    }
+    files
+}
+
+/// Disk usage of the log directory, for the log settings panel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct LogStats {
+    /// Size of the current log file in bytes.
+    #[ts(type = "number")]
+    pub current_bytes: u64,
+    /// Combined size of all archived log files in bytes.
+    #[ts(type = "number")]
+    pub archive_bytes: u64,
+    /// Number of archived log files, plain or compressed.
+    pub archive_count: usize,
+}
+
+/// Measure the current log file and the archives in the log directory.
+///
+/// Returns all zeros if the platform has no log directory or it does not exist yet.
+pub(crate) fn log_stats() -> LogStats {
+    log_dir().map(|dir| log_stats_in(&dir)).unwrap_or_default()
+}
+
+/// Core of [`log_stats`], separated for testability. Archives are recognized like in [`list_archives`].
+fn log_stats_in(dir: &Path) -> LogStats {
+    let current_bytes = fs::metadata(dir.join(format!("{LOG_FILE_NAME}.log"))).map_or(0, |meta| meta.len());
+    let archives = list_archives(dir).unwrap_or_default();
+    LogStats {
+        current_bytes,
+        archive_bytes: archives.iter().filter_map(|a| fs::metadata(&a.path).ok()).map(|meta| meta.len()).sum(),
+        archive_count: archives.len(),
+    }
+}
+
+/// Filter for [`query_log`]: the exact logger name and the least severe level to include.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct LogQuery<'a> {
+    pub logger: Option<&'a str>,
+    pub min_level: Option<Level>,
+}
+
+impl LogQuery<'_> {
+    /// Whether a log line matches the filter. Lines that are not log entries (e.g. stack trace continuations)
+    /// never match.
+    fn matches(&self, line: &str) -> bool {
+        let Some((level, logger)) = parse_line(line) else { return false };
+        self.min_level.is_none_or(|min| level <= min) && self.logger.is_none_or(|name| logger == name)
+    }
+}
+
+/// Parse the level and logger name back out of a line written by [`format_line`].
+///
+/// Both columns are fixed-width: a 5-character level after the timestamp, then the padded logger name in brackets.
+/// A logger name that was middle-truncated to fit its column is returned as written.
+fn parse_line(line: &str) -> Option<(Level, &str)> {
+    let rest = line.get(TIMESTAMP_LEN..)?.strip_prefix(' ')?;
+    let level = rest.get(..5)?.trim_end().parse::<Level>().ok()?;
+    let (logger, _) = rest.get(5..)?.strip_prefix(" [")?.split_once("] (")?;
+    Some((level, logger.trim_end()))
+}
+
+/// Return up to `limit` lines of the current log file that match `query`, newest first.
+///
+/// Reads the whole file, which the size rotation keeps small. Returns an empty list if the log file does not exist
+/// yet, and an error if the platform has no log directory or the file cannot be read.
+pub(crate) fn query_log(query: LogQuery, limit: usize) -> Result<Vec<String>, String> {
+    let dir = log_dir().ok_or("No log directory on this platform")?;
+    let path = dir.join(format!("{LOG_FILE_NAME}.log"));
+    match fs::read(&path) {
+        Ok(bytes) => Ok(filter_lines(&String::from_utf8_lossy(&bytes), query, limit)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+    }
+}
+
+/// Select up to `limit` lines of `content` that match `query`, newest first.
+fn filter_lines(content: &str, query: LogQuery, limit: usize) -> Vec<String> {
+    content.lines().rev().filter(|line| query.matches(line)).take(limit).map(str::to_string).collect()
+}
+
+// ---- Log formatting -------------------------------------------------------------
+
+/// Unit Separator, used as delimiter between logger name, optional context, and message from the JS frontend.
+const SEP: char = '\x1F';
+
+/// Display width for the logger name in log output. Matches bit-log's default. Names are right-padded or
+/// middle-truncated to this width.
+const LOGGER_NAME_WIDTH: usize = 20;
+
+/// Display width for the file path in log output. Paths longer than this are middle-truncated with "...".
+const FILE_PATH_WIDTH: usize = 30;
+
+/// Environment variable that overrides [`LOGGER_NAME_WIDTH`].
+const LOGGER_WIDTH_ENV: &str = "DAYSTROM_LOGGER_WIDTH";
+
+/// Environment variable that overrides [`FILE_PATH_WIDTH`].
+const FILE_WIDTH_ENV: &str = "DAYSTROM_FILE_WIDTH";
+
+/// Smallest accepted column width, leaving room for the "..." of a middle-truncated value.
+const MIN_COLUMN_WIDTH: usize = 5;
+
+/// Widths of the padded columns in a log line, read once in [`build_plugin`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ColumnWidths {
+    /// Width of the logger name column.
+    logger: usize,
+    /// Width of the file path column.
+    file: usize,
+}
+
+impl Default for ColumnWidths {
+    fn default() -> Self {
+        ColumnWidths { logger: LOGGER_NAME_WIDTH, file: FILE_PATH_WIDTH }
+    }
+}
+
+/// Resolve a column width from the raw environment variable value.
+///
+/// Falls back to `default` when the variable is unset, not an integer, or below [`MIN_COLUMN_WIDTH`].
+fn column_width(value: Option<&str>, default: usize) -> usize {
+    value
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|width| *width >= MIN_COLUMN_WIDTH)
+        .unwrap_or(default)
+}
+
+/// Environment variable that disables coloured output when set to a non-empty value (<https://no-color.org>).
+const NO_COLOR_ENV: &str = "NO_COLOR";
+
+/// Whether the `Stdout` target should use colours: only on a terminal and when `NO_COLOR` is unset or empty.
+///
+/// Colours are switched off through the `colored` crate's global override in [`build_plugin`]; the `LogDir` target
+/// never colours its output anyway.
+fn stdout_colours_enabled(no_color: Option<&OsStr>, is_terminal: bool) -> bool {
+    is_terminal && no_color.is_none_or(|value| value.is_empty())
+}
+
+/// Formatter for the `Stdout` target: the log line with a colourised level.
+fn format_stdout(
+    callback: fern::FormatCallback,
+    message: &std::fmt::Arguments,
+    record: &log::Record,
+    widths: ColumnWidths,
+) {
+    callback.finish(format_args!("{}", format_line(message, record, true, widths)));
+}
+
+/// Formatter for the `LogDir` target: the plain log line without ANSI escapes.
+///
+/// Also drives the runtime rotation, since only the log file is affected by a date change.
+fn format_file(
+    callback: fern::FormatCallback,
+    message: &std::fmt::Arguments,
+    record: &log::Record,
+    widths: ColumnWidths,
+) {
+    check_runtime_rotation();
+    callback.finish(format_args!("{}", format_line(message, record, false, widths)));
+}
+
+/// Build the log line matching bit-log's format: `{timestamp} {LEVEL} [{loggerName}] ({file}:{line}): {message}`
+///
+/// For JS-originated logs, the logger name is embedded in the message as `name\x1Fmessage`, or with a context map as
+/// `name\x1Fcontext_json\x1Fmessage`; the context is appended to the message as compact JSON. For Rust-originated
+/// logs, `record.target()` is used as the logger name. The level is colourised only when `coloured` is set; the
+/// logger name and file path are fitted to `widths`.
+fn format_line(message: &std::fmt::Arguments, record: &log::Record, coloured: bool, widths: ColumnWidths) -> String {
+    format!("{} {}", format_timestamp(), format_body(message, record, coloured, widths))
+}
+
+/// Build everything of a [`format_line`] log line after the timestamp.
+fn format_body(message: &std::fmt::Arguments, record: &log::Record, coloured: bool, widths: ColumnWidths) -> String {
+    let level = if coloured {
+        coloured_level(record.level())
+    } else {
+        fit(&record.level().to_string(), 5)
+    };
     let file = record.file().unwrap_or("unknown");
     let file = file.strip_prefix("src/").unwrap_or(file);
-    let file_display = fit_path(file, FILE_PATH_WIDTH);
+    let file_display = fit_middle(file, widths.file);
     let line = record.line().unwrap_or(0);
 
     let raw = message.to_string();
-    let (origin, logger_name, msg) = match raw.split_once(SEP) {
-        Some((name, rest)) => ("Frontend", name, rest),
-        None => ("Backend ", record.target(), raw.as_str()),
-    };
-    let target = fit(logger_name, LOGGER_NAME_WIDTH);
+    let parts = split_message(&raw, record);
+    let target = fit_middle(parts.logger, widths.logger);
+    let (origin, msg) = (parts.origin, parts.message);
+
+    match parts.context {
+        Some(context) => format!(
+            "{level} [{target}] ({origin:<8}: {file_display}: {line:>4}): {msg} {}",
+            serde_json::Value::Object(context)
+        ),
+        None => format!("{level} [{target}] ({origin:<8}: {file_display}: {line:>4}): {msg}"),
+    }
+}
 
-    callback.finish(format_args!(
-        "{timestamp} {level} [{target}] ({origin}: {file_display}: {line:>4}): {msg}"
-    ));
+/// Formatter for the optional JSON Lines targets (file and stderr): one JSON object per line.
+fn format_json(
+    callback: fern::FormatCallback,
+    message: &std::fmt::Arguments,
+    record: &log::Record,
+) {
+    callback.finish(format_args!("{}", json_line(message, record)));
+}
+
+/// Build a single JSON log entry with the same fields as [`format_line`], but unpadded and untruncated.
+fn json_line(message: &std::fmt::Arguments, record: &log::Record) -> String {
+    let file = record.file().unwrap_or("unknown");
+    let raw = message.to_string();
+    let parts = split_message(&raw, record);
+
+    let mut entry = serde_json::json!({
+        "timestamp": format_timestamp(),
+        "level": record.level().as_str(),
+        "logger": parts.logger,
+        "origin": parts.origin,
+        "file": file.strip_prefix("src/").unwrap_or(file),
+        "line": record.line().unwrap_or(0),
+        "message": parts.message,
+    });
+    if let Some(context) = parts.context {
+        entry["context"] = serde_json::Value::Object(context);
+    }
+    entry.to_string()
+}
+
+/// A raw log message split by [`split_message`].
+struct MessageParts<'a> {
+    /// `Frontend` or `Backend`.
+    origin: &'static str,
+    logger: &'a str,
+    /// Key/value context sent along by the frontend, if any and not empty.
+    context: Option<serde_json::Map<String, serde_json::Value>>,
+    message: &'a str,
+}
+
+/// Split a raw log message into origin, logger name, context, and message.
+///
+/// JS-originated messages carry their logger name as `name\x1Fmessage`, or `name\x1Fcontext_json\x1Fmessage` with a
+/// JSON object as context; everything else uses `record.target()`. If the middle part is not a JSON object, the
+/// whole rest is taken as the message, as in the single-separator form.
+fn split_message<'a>(raw: &'a str, record: &'a log::Record) -> MessageParts<'a> {
+    let Some((logger, rest)) = raw.split_once(SEP) else {
+        return MessageParts { origin: "Backend", logger: record.target(), context: None, message: raw };
+    };
+    let context = rest.split_once(SEP).and_then(|(context, message)| {
+        let context = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(context).ok()?;
+        Some((context, message))
+    });
+    match context {
+        Some((context, message)) => MessageParts {
+            origin: "Frontend",
+            logger,
+            context: (!context.is_empty()).then_some(context),
+            message,
+        },
+        None => MessageParts { origin: "Frontend", logger, context: None, message: rest },
+    }
 }
 
 /// Format the current local time as ISO 8601 with milliseconds and timezone offset.
 /// Example: `2026-02-20T14:30:45.123+01:00`
 fn format_timestamp() -> String {
     let now = TimezoneStrategy::UseLocal.get_now();
-    let format = time::format_description::parse(
-        "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]\
-         [offset_hour sign:mandatory]:[offset_minute]",
-    )
-    .expect("invalid time format");
-    now.format(&format).unwrap_or_else(|_| "????-??-??T??:??:??.???+??:??".to_string())
+    now.format(TIMESTAMP_FORMAT).unwrap_or_else(|_| fallback_timestamp())
+}
+
+/// Format the current UTC time like [`format_timestamp`], for when the local time cannot be formatted.
+///
+/// Ends with the Unix epoch as a last resort, so every log line starts with a date [`last_log_date`] can parse and
+/// the rotation never mistakes the file for garbage.
+fn fallback_timestamp() -> String {
+    time::OffsetDateTime::now_utc()
+        .format(TIMESTAMP_FORMAT)
+        .unwrap_or_else(|_| "1970-01-01T00:00:00.000+00:00".to_string())
 }
 
 /// Colorize a log level string matching bit-log's color scheme:
@@ -467,14 +1784,15 @@ fn fit(s: &str, width: usize) -> String {
     }
 }
 
-/// Pad or middle-truncate a path to exactly `width` characters. Keeps the beginning and end of the path, replaces
-/// the middle with "...". Short strings are right-padded with spaces.
-fn fit_path(s: &str, width: usize) -> String {
+/// Pad or middle-truncate a string to exactly `width` characters. Keeps the beginning and end (e.g. the top-level
+/// directory and file name of a path, or the prefix and suffix of a logger name), replaces the middle with "...".
+/// Short strings are right-padded with spaces.
+fn fit_middle(s: &str, width: usize) -> String {
     let char_count = s.chars().count();
     if char_count <= width {
         return format!("{s:<width$}");
     }
-    // 3 chars for "...", split remaining space: more at end (filename matters most)
+    // 3 chars for "...", split remaining space: more at end (file name matters most)
     let available = width - 3;
     let end_len = available.div_ceil(2);
     let start_len = available - end_len;
@@ -505,6 +1823,9 @@ mod tests {
         )
     }
 
+    /// Offset used by [`log_line`].
+    const LOG_LINE_OFFSET: time::UtcOffset = time::macros::offset!(+1);
+
     /// Mutex to serialize tests that read/write the global [`ROTATION_STATE`].
     static TEST_LOCK: Mutex<()> = Mutex::new(());
 
@@ -536,7 +1857,7 @@ mod tests {
     #[test]
     fn last_log_date_missing_file() {
         let path = std::env::temp_dir().join("daystrom_nonexistent.log");
-        assert!(last_log_date(&path).is_none());
+        assert!(last_log_date(&path, LOG_LINE_OFFSET).is_none());
     }
 
     #[test]
@@ -544,75 +1865,458 @@ mod tests {
         let dir = test_dir("last_log_date_empty");
         let path = dir.join("test.log");
         fs::write(&path, "").unwrap();
-        assert!(last_log_date(&path).is_none());
+        assert!(last_log_date(&path, LOG_LINE_OFFSET).is_none());
+    }
+
+    #[test]
+    fn last_log_date_garbage_content() {
+        let dir = test_dir("last_log_date_garbage");
+        let path = dir.join("test.log");
+        fs::write(&path, "just some random text\nno timestamps here\n").unwrap();
+        assert!(last_log_date(&path, LOG_LINE_OFFSET).is_none());
+    }
+
+    #[test]
+    fn last_log_date_single_entry() {
+        let dir = test_dir("last_log_date_single");
+        let path = dir.join("test.log");
+        fs::write(&path, log_line("2026-02-20")).unwrap();
+
+        let date = last_log_date(&path, LOG_LINE_OFFSET).unwrap();
+        let fmt = time::macros::format_description!("[year]-[month]-[day]");
+        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+    }
+
+    #[test]
+    fn last_log_date_returns_last_date() {
+        let dir = test_dir("last_log_date_multi");
+        let path = dir.join("test.log");
+        let content = format!("{}{}", log_line("2026-02-19"), log_line("2026-02-20"));
+        fs::write(&path, content).unwrap();
+
+        let date = last_log_date(&path, LOG_LINE_OFFSET).unwrap();
+        let fmt = time::macros::format_description!("[year]-[month]-[day]");
+        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+    }
+
+    #[test]
+    fn last_log_date_skips_trailing_stacktrace() {
+        let dir = test_dir("last_log_date_stacktrace");
+        let path = dir.join("test.log");
+        let content = format!(
+            "{}  at SomeClass.method(File.java:42)\n  at Another.call(Other.java:99)\n",
+            log_line("2026-02-20")
+        );
+        fs::write(&path, content).unwrap();
+
+        let date = last_log_date(&path, LOG_LINE_OFFSET).unwrap();
+        let fmt = time::macros::format_description!("[year]-[month]-[day]");
+        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+    }
+
+    #[test]
+    fn last_log_date_skips_long_trailing_block() {
+        let dir = test_dir("last_log_date_long_tail");
+        let path = dir.join("test.log");
+
+        // A 10 KB block without timestamps after the last entry, e.g. a wrapped stack trace
+        let mut content = format!("{}{}", log_line("2026-02-19"), log_line("2026-02-20"));
+        content.push_str(&format!("{}\n", "    at frame ".repeat(800)));
+        assert!(content.len() > 10 * 1024);
+
+        fs::write(&path, content).unwrap();
+
+        let date = last_log_date(&path, LOG_LINE_OFFSET).unwrap();
+        let fmt = time::macros::format_description!("[year]-[month]-[day]");
+        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+    }
+
+    #[test]
+    fn last_log_date_uses_local_offset() {
+        let dir = test_dir("last_log_date_offset");
+        let path = dir.join("test.log");
+        fs::write(&path, "2026-03-28T23:59:30.000+01:00 INFO  [Test] (Backend : test.rs: 1): message\n").unwrap();
+
+        let fmt = time::macros::format_description!("[year]-[month]-[day]");
+        let winter = last_log_date(&path, LOG_LINE_OFFSET).unwrap();
+        assert_eq!(winter.format(&fmt).unwrap(), "2026-03-28");
+        let summer = last_log_date(&path, time::macros::offset!(+2)).unwrap();
+        assert_eq!(summer.format(&fmt).unwrap(), "2026-03-29");
+    }
+
+    #[test]
+    fn last_log_date_handles_large_file() {
+        let dir = test_dir("last_log_date_large");
+        let path = dir.join("test.log");
+
+        // Create a file larger than TAIL_READ_SIZE (4096) with the timestamp near the end
+        let padding = "x".repeat(200);
+        let mut content = String::new();
+        for _ in 0..30 {
+            content.push_str(&padding);
+            content.push('\n');
+        }
+        content.push_str(&log_line("2026-02-20"));
+        assert!(content.len() > TAIL_READ_SIZE as usize);
+
+        fs::write(&path, content).unwrap();
+
+        let date = last_log_date(&path, LOG_LINE_OFFSET).unwrap();
+        let fmt = time::macros::format_description!("[year]-[month]-[day]");
+        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+    }
+
+    // -- tail_lines --
+
+    #[test]
+    fn tail_lines_fewer_than_requested() {
+        let dir = test_dir("tail_lines_fewer");
+        let path = dir.join("test.log");
+        fs::write(&path, "first\nsecond\n").unwrap();
+
+        assert_eq!(tail_lines(&path, 5).unwrap(), ["first", "second"]);
+    }
+
+    #[test]
+    fn tail_lines_returns_last_n() {
+        let dir = test_dir("tail_lines_last_n");
+        let path = dir.join("test.log");
+        fs::write(&path, "one\ntwo\nthree\nfour\n").unwrap();
+
+        assert_eq!(tail_lines(&path, 2).unwrap(), ["three", "four"]);
+    }
+
+    #[test]
+    fn tail_lines_larger_than_window() {
+        let dir = test_dir("tail_lines_large");
+        let path = dir.join("test.log");
+
+        // 100 lines of ~100 bytes each: the requested lines span more than one TAIL_READ_SIZE window
+        let content: String = (0..100).map(|i| format!("{i:03} {}\n", "x".repeat(96))).collect();
+        assert!(content.len() > 2 * TAIL_READ_SIZE as usize);
+        fs::write(&path, content).unwrap();
+
+        let lines = tail_lines(&path, 60).unwrap();
+        assert_eq!(lines.len(), 60);
+        assert!(lines[0].starts_with("040 "), "first line should be complete: {:?}", lines[0]);
+        assert!(lines[59].starts_with("099 "));
+    }
+
+    #[test]
+    fn fallback_timestamp_is_parseable() {
+        let timestamp = fallback_timestamp();
+        let date_fmt = time::macros::format_description!("[year]-[month]-[day]");
+        assert!(time::Date::parse(&timestamp[..10], &date_fmt).is_ok(), "no date in '{timestamp}'");
+        let parsed = time::OffsetDateTime::parse(&timestamp, TIMESTAMP_FORMAT).unwrap();
+        assert_eq!(parsed.offset(), time::UtcOffset::UTC);
+    }
+
+    #[test]
+    fn parse_line_reads_fixed_columns() {
+        let line = "2026-02-20T14:30:45.123+01:00 WARN  [Entitlements        ] (Backend : game/mod.rs:   12): x";
+        assert_eq!(parse_line(line), Some((Level::Warn, "Entitlements")));
+        assert_eq!(parse_line("    at frame (main.js:1)"), None);
+        assert_eq!(parse_line(""), None);
+    }
+
+    #[test]
+    fn filter_lines_by_logger_and_level() {
+        let content = [
+            "2026-02-20T14:30:45.123+01:00 INFO  [Entitlements        ] (Backend : a.rs:    1): first",
+            "2026-02-20T14:30:46.123+01:00 WARN  [Entitlements        ] (Backend : a.rs:    2): second",
+            "    continuation of second",
+            "2026-02-20T14:30:47.123+01:00 ERROR [Game                ] (Backend : b.rs:    3): third",
+            "2026-02-20T14:30:48.123+01:00 ERROR [Entitlements        ] (Backend : a.rs:    4): fourth",
+        ]
+        .join("\n");
+
+        let query = LogQuery { logger: Some("Entitlements"), min_level: Some(Level::Warn) };
+        let lines = filter_lines(&content, query, 10);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("fourth") && lines[1].ends_with("second"), "newest first: {lines:?}");
+
+        let lines = filter_lines(&content, LogQuery { min_level: Some(Level::Error), ..LogQuery::default() }, 1);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].ends_with("fourth"));
+
+        assert_eq!(filter_lines(&content, LogQuery::default(), 10).len(), 4);
+    }
+
+    #[test]
+    fn tail_lines_missing_file() {
+        let path = std::env::temp_dir().join("daystrom_test_tail_lines_nonexistent.log");
+        assert_eq!(tail_lines(&path, 5).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    // -- rotation periods --
+
+    fn date(year: i32, month: time::Month, day: u8) -> time::Date {
+        time::Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn log_stats_counts_archives() {
+        let dir = test_dir("log_stats");
+        assert_eq!(log_stats_in(&dir.join("missing")), LogStats::default());
+
+        fs::write(dir.join("project-daystrom.log"), "12345").unwrap();
+        fs::write(dir.join("project-daystrom_2026-02-18.log"), "123").unwrap();
+        fs::write(dir.join("project-daystrom_2026-02-19_10-00-00.log.gz"), "12").unwrap();
+        fs::write(dir.join("project-daystrom-json.log"), "ignored").unwrap();
+        fs::write(dir.join("project-daystrom_notes.txt"), "ignored").unwrap();
+
+        let stats = log_stats_in(&dir);
+        assert_eq!(stats, LogStats { current_bytes: 5, archive_bytes: 5, archive_count: 2 });
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn clear_logs_keeps_new_archive() {
+        let dir = test_dir("clear_logs");
+        fs::write(dir.join("project-daystrom.log"), log_line("2026-02-19")).unwrap();
+        fs::write(dir.join("project-daystrom_2026-02-17.log.gz"), "old").unwrap();
+        fs::write(dir.join("project-daystrom_2026-02-18.log"), "old").unwrap();
+
+        assert_eq!(clear_logs_in(&dir, true), Ok(3));
+
+        let archives = list_archives(&dir).unwrap();
+        assert_eq!(archives.len(), 1);
+        assert!(archives[0].name.starts_with("project-daystrom_2026-02-19_"), "unexpected {}", archives[0].name);
+        assert!(archives[0].name.ends_with(".log.gz"));
+        assert_eq!(fs::metadata(dir.join("project-daystrom.log")).unwrap().len(), 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn clear_logs_without_deleting_archives() {
+        let dir = test_dir("clear_logs_keep");
+        fs::write(dir.join("project-daystrom.log"), log_line("2026-02-19")).unwrap();
+        fs::write(dir.join("project-daystrom_2026-02-18.log"), "old").unwrap();
+
+        assert_eq!(clear_logs_in(&dir, false), Ok(1));
+        assert_eq!(list_archives(&dir).unwrap().len(), 2);
+        // Nothing left to archive
+        assert_eq!(clear_logs_in(&dir, false), Ok(0));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotate_now_returns_archive_name() {
+        let dir = test_dir("rotate_now");
+        fs::write(dir.join("project-daystrom.log"), log_line("2026-02-19")).unwrap();
+
+        let now = time::macros::datetime!(2026-02-19 16:05:09 +01:00);
+        let name = rotate_now_in(&dir, now).unwrap().expect("should archive");
+        assert!(name.starts_with("project-daystrom_2026-02-19_16-05-09"), "unexpected {name}");
+        assert!(dir.join(&name).exists(), "archive should exist");
+        assert_eq!(fs::metadata(dir.join("project-daystrom.log")).unwrap().len(), 0);
+        // Nothing left to archive
+        assert_eq!(rotate_now_in(&dir, now), Ok(None));
+
+        // A second rotation within the same second finds the slot taken
+        fs::write(dir.join("project-daystrom.log"), log_line("2026-02-19")).unwrap();
+        let e = rotate_now_in(&dir, now).unwrap_err();
+        assert!(e.contains("already exists"), "unexpected {e}");
+        assert_ne!(fs::metadata(dir.join("project-daystrom.log")).unwrap().len(), 0, "log should be untouched");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotate_now_rejects_log_without_leading_timestamp() {
+        let dir = test_dir("rotate_now_no_timestamp");
+        let content = format!("continued stack trace\n{}", log_line("2026-02-19"));
+        fs::write(dir.join("project-daystrom.log"), &content).unwrap();
+
+        let e = rotate_now_in(&dir, time::OffsetDateTime::now_utc()).unwrap_err();
+        assert!(e.contains("does not start with a timestamp"), "unexpected {e}");
+        assert_eq!(fs::read_to_string(dir.join("project-daystrom.log")).unwrap(), content, "log should be untouched");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn archive_naming_default_is_flat() {
+        let naming = ArchiveNaming::default();
+        let dir = Path::new("/logs");
+        let day = date(2026, time::Month::February, 20);
+        assert_eq!(naming.archive_path(dir, day, None), dir.join("project-daystrom_2026-02-20.log"));
+        assert_eq!(
+            naming.archive_path(dir, day, Some("10-00-00")),
+            dir.join("project-daystrom_2026-02-20_10-00-00.log")
+        );
+
+        assert_eq!(naming.parse_file_name("project-daystrom_2026-02-20.log.gz"), Some((day, None)));
+        assert_eq!(
+            naming.parse_file_name("project-daystrom_2026-02-20_10-00-00.log"),
+            Some((day, Some("10-00-00".to_string())))
+        );
+        assert_eq!(naming.parse_file_name("project-daystrom.log"), None);
+        assert_eq!(naming.parse_file_name("project-daystrom-json.log"), None);
+        assert_eq!(naming.parse_file_name("project-daystrom_2026-02-20_notes.log"), None);
+    }
+
+    #[test]
+    fn archive_naming_custom_templates() {
+        let naming = ArchiveNaming::new(Some("{year}/{month}"), Some("{app}-{name}.{date}")).unwrap();
+        let dir = Path::new("/logs");
+        let day = date(2026, time::Month::February, 20);
+        let app = env!("TAURI_IDENTIFIER");
+
+        let plain = naming.archive_path(dir, day, None);
+        assert_eq!(plain, dir.join(format!("2026/2026-02/{app}-project-daystrom.2026-02-20.log")));
+        // A template without {time} gets it appended
+        let timed = naming.archive_path(dir, day, Some("10-00-00"));
+        assert_eq!(timed, dir.join(format!("2026/2026-02/{app}-project-daystrom.2026-02-20_10-00-00.log")));
+
+        let file_name = timed.file_name().unwrap().to_string_lossy();
+        assert_eq!(naming.parse_file_name(&file_name), Some((day, Some("10-00-00".to_string()))));
+        assert_eq!(naming.parse_file_name("project-daystrom_2026-02-20.log"), None);
+    }
+
+    #[test]
+    fn archive_naming_rejects_invalid_templates() {
+        assert!(ArchiveNaming::new(None, Some("{name}_{time}")).is_err(), "missing {{date}}");
+        assert!(ArchiveNaming::new(None, Some("{name}_{day}")).is_err(), "unknown placeholder");
+        assert!(ArchiveNaming::new(None, Some("{name}_{date")).is_err(), "unclosed placeholder");
+        assert!(ArchiveNaming::new(None, Some("{month}/{name}_{date}")).is_err(), "separator in name");
+        assert!(ArchiveNaming::new(Some("{time}"), None).is_err(), "time in folder");
+        assert!(ArchiveNaming::new(Some("../elsewhere"), None).is_err(), "folder outside the log directory");
+        assert_eq!(ArchiveNaming::new(Some(" "), Some("")), Ok(ArchiveNaming::default()));
+    }
+
+    #[test]
+    fn list_archives_finds_archives_in_subfolders() {
+        let dir = test_dir("list_archives_subfolders");
+        let naming = ArchiveNaming::new(Some("{month}"), None).unwrap();
+        fs::create_dir_all(dir.join("2026-01")).unwrap();
+        fs::create_dir_all(dir.join("2026-02")).unwrap();
+        fs::write(dir.join("2026-02/project-daystrom_2026-02-19_10-00-00.log.gz"), "").unwrap();
+        fs::write(dir.join("2026-02/project-daystrom_2026-02-19.log.gz"), "").unwrap();
+        fs::write(dir.join("2026-01/project-daystrom_2026-01-31.log"), "").unwrap();
+        fs::write(dir.join("project-daystrom_2026-01-30.log"), "").unwrap();
+        fs::write(dir.join("project-daystrom.log"), "").unwrap();
+
+        let names: Vec<String> = list_archives_with(&dir, &naming).unwrap().into_iter().map(|a| a.name).collect();
+        assert_eq!(names, [
+            "project-daystrom_2026-01-30.log",
+            "project-daystrom_2026-01-31.log",
+            "project-daystrom_2026-02-19.log.gz",
+            "project-daystrom_2026-02-19_10-00-00.log.gz",
+        ]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cleanup_removes_emptied_subfolders() {
+        let dir = test_dir("cleanup_subfolders");
+        fs::create_dir_all(dir.join("2026-01")).unwrap();
+        fs::write(dir.join("2026-01/project-daystrom_2026-01-10.log.gz"), "").unwrap();
+
+        cleanup_old_archives(&dir, date(2026, time::Month::March, 1), 30);
+        assert!(!dir.join("2026-01").exists(), "empty subfolder should be removed");
+        assert!(dir.exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn iso_week_parsing() {
+        assert_eq!(parse_iso_week("2026-W08"), Some(date(2026, time::Month::February, 22)));
+        assert_eq!(parse_iso_week("2026-W01"), Some(date(2026, time::Month::January, 4)));
+        assert_eq!(parse_iso_week("2026-W54"), None);
+        assert_eq!(parse_iso_week("2026-08"), None);
+        assert_eq!(parse_iso_week("26-W08"), None);
+    }
+
+    #[test]
+    fn cleanup_ages_out_weekly_archives() {
+        let dir = test_dir("cleanup_weekly");
+        fs::write(dir.join("project-daystrom_2026-W01.log"), "old").unwrap();
+        fs::write(dir.join("project-daystrom_2026-W08.log.gz"), "recent").unwrap();
+
+        // 2026-W01 ends on January 4, 2026-W08 on February 22
+        cleanup_old_archives(&dir, date(2026, time::Month::March, 1), 30);
+        assert!(!dir.join("project-daystrom_2026-W01.log").exists(), "old weekly archive should be deleted");
+        assert!(dir.join("project-daystrom_2026-W08.log.gz").exists(), "recent weekly archive should be kept");
+
+        let _ = fs::remove_dir_all(&dir);
     }
 
     #[test]
-    fn last_log_date_garbage_content() {
-        let dir = test_dir("last_log_date_garbage");
-        let path = dir.join("test.log");
-        fs::write(&path, "just some random text\nno timestamps here\n").unwrap();
-        assert!(last_log_date(&path).is_none());
+    fn log_level_parsing() {
+        assert_eq!(log_level(None, DEFAULT_LOG_LEVEL), LevelFilter::Debug);
+        assert_eq!(log_level(Some("trace"), DEFAULT_LOG_LEVEL), LevelFilter::Trace);
+        assert_eq!(log_level(Some(" Info "), DEFAULT_LOG_LEVEL), LevelFilter::Info);
+        assert_eq!(log_level(Some("WARN"), DEFAULT_LOG_LEVEL), LevelFilter::Warn);
+        assert_eq!(log_level(Some("error"), DEFAULT_LOG_LEVEL), LevelFilter::Error);
+        assert_eq!(log_level(Some("off"), DEFAULT_LOG_LEVEL), LevelFilter::Debug);
+        assert_eq!(log_level(Some("verbose"), DEFAULT_LOG_LEVEL), LevelFilter::Debug);
     }
 
     #[test]
-    fn last_log_date_single_entry() {
-        let dir = test_dir("last_log_date_single");
-        let path = dir.join("test.log");
-        fs::write(&path, log_line("2026-02-20")).unwrap();
+    fn target_level_falls_back_to_global() {
+        let global = log_level(Some("trace"), DEFAULT_LOG_LEVEL);
+        assert_eq!(log_level(None, global), LevelFilter::Trace);
+        assert_eq!(log_level(Some("info"), global), LevelFilter::Info);
+        assert_eq!(log_level(Some("loud"), global), LevelFilter::Trace);
+    }
 
-        let date = last_log_date(&path).unwrap();
-        let fmt = time::macros::format_description!("[year]-[month]-[day]");
-        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+    #[test]
+    fn rotation_period_parsing() {
+        assert_eq!(rotation_period(None), RotationPeriod::Daily);
+        assert_eq!(rotation_period(Some("weekly")), RotationPeriod::Weekly);
+        assert_eq!(rotation_period(Some(" Monthly ")), RotationPeriod::Monthly);
+        assert_eq!(rotation_period(Some("hourly")), RotationPeriod::Daily);
     }
 
     #[test]
-    fn last_log_date_returns_last_date() {
-        let dir = test_dir("last_log_date_multi");
-        let path = dir.join("test.log");
-        let content = format!("{}{}", log_line("2026-02-19"), log_line("2026-02-20"));
-        fs::write(&path, content).unwrap();
+    fn log_compression_parsing() {
+        assert_eq!(log_compression(None), Ok(LogCompression::Gzip));
+        assert_eq!(log_compression(Some("gzip")), Ok(LogCompression::Gzip));
+        assert_eq!(log_compression(Some(" NONE ")), Ok(LogCompression::None));
+        assert_eq!(log_compression(Some("Zstd")), Ok(LogCompression::Zstd));
+        assert!(log_compression(Some("brotli")).is_err());
+    }
 
-        let date = last_log_date(&path).unwrap();
-        let fmt = time::macros::format_description!("[year]-[month]-[day]");
-        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+    #[test]
+    fn rotation_period_start() {
+        // 2026-02-19 is a Thursday
+        let thursday = date(2026, time::Month::February, 19);
+        assert_eq!(RotationPeriod::Daily.start(thursday), thursday);
+        assert_eq!(RotationPeriod::Weekly.start(thursday), date(2026, time::Month::February, 16));
+        assert_eq!(RotationPeriod::Monthly.start(thursday), date(2026, time::Month::February, 1));
     }
 
     #[test]
-    fn last_log_date_skips_trailing_stacktrace() {
-        let dir = test_dir("last_log_date_stacktrace");
-        let path = dir.join("test.log");
-        let content = format!(
-            "{}  at SomeClass.method(File.java:42)\n  at Another.call(Other.java:99)\n",
-            log_line("2026-02-20")
-        );
-        fs::write(&path, content).unwrap();
+    fn rotate_monthly_keeps_current_month() {
+        let dir = test_dir("rotate_monthly_current");
+        let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+        let content = log_line(&format_date(RotationPeriod::Monthly.start(today_date())));
+        fs::write(&log_file, &content).unwrap();
 
-        let date = last_log_date(&path).unwrap();
-        let fmt = time::macros::format_description!("[year]-[month]-[day]");
-        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Monthly);
+
+        assert_eq!(fs::read_to_string(&log_file).unwrap(), content, "log file should be unchanged");
     }
 
     #[test]
-    fn last_log_date_handles_large_file() {
-        let dir = test_dir("last_log_date_large");
-        let path = dir.join("test.log");
-
-        // Create a file larger than TAIL_READ_SIZE (4096) with the timestamp near the end
-        let padding = "x".repeat(200);
-        let mut content = String::new();
-        for _ in 0..30 {
-            content.push_str(&padding);
-            content.push('\n');
-        }
-        content.push_str(&log_line("2026-02-20"));
-        assert!(content.len() > TAIL_READ_SIZE as usize);
+    fn rotate_monthly_archives_previous_month() {
+        let dir = test_dir("rotate_monthly_previous");
+        let last_month = RotationPeriod::Monthly.start(today_date()) - time::Duration::days(1);
+        let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+        fs::write(&log_file, log_line(&format_date(last_month))).unwrap();
 
-        fs::write(&path, content).unwrap();
+        // Generous retention, so the archive survives cleanup on any day of the month
+        rotate_logs_in(&dir, 365, RotationPeriod::Monthly);
 
-        let date = last_log_date(&path).unwrap();
-        let fmt = time::macros::format_description!("[year]-[month]-[day]");
-        assert_eq!(date.format(&fmt).unwrap(), "2026-02-20");
+        assert!(!log_file.exists(), "original log should be gone");
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{}.log.gz", format_date(last_month)));
+        assert!(archive.exists(), "archive should be named after the last entry");
     }
 
     // -- rotate_logs_in --
@@ -624,13 +2328,39 @@ mod tests {
         let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
         fs::write(&log_file, log_line(&yesterday)).unwrap();
 
-        rotate_logs_in(&dir);
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
 
         assert!(!log_file.exists(), "original log should be gone");
-        let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}.log"));
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}.log.gz"));
         assert!(archive.exists(), "archive should exist");
     }
 
+    #[test]
+    fn rotate_compressed_archive_round_trips() {
+        use std::io::Read;
+
+        let dir = test_dir("rotate_gzip_round_trip");
+        let yesterday = days_ago_str(1);
+        let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+        let content = format!("{}{}", log_line(&yesterday), log_line(&yesterday));
+        fs::write(&log_file, &content).unwrap();
+
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
+
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}.log.gz"));
+        assert!(archive.to_string_lossy().ends_with(".log.gz"));
+        assert!(
+            !dir.join(format!("{LOG_FILE_NAME}_{yesterday}.log")).exists(),
+            "plain archive should be replaced by the compressed one"
+        );
+
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(fs::File::open(&archive).unwrap())
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, content.as_bytes());
+    }
+
     #[test]
     fn rotate_keeps_todays_file() {
         let dir = test_dir("rotate_today");
@@ -638,18 +2368,36 @@ mod tests {
         let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
         fs::write(&log_file, log_line(&today)).unwrap();
 
-        rotate_logs_in(&dir);
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
 
         assert!(log_file.exists(), "today's log should remain");
     }
 
+    #[test]
+    fn rotate_archives_entry_before_local_midnight() {
+        let dir = test_dir("rotate_before_midnight");
+        let now = time::OffsetDateTime::now_local().unwrap_or_else(|_| time::OffsetDateTime::now_utc());
+        let midnight = now.replace_time(time::Time::MIDNIGHT);
+        // Written one second before local midnight, but in an offset one hour ahead, so the line reads as today
+        let ahead = time::UtcOffset::from_whole_seconds(now.offset().whole_seconds() + 3600).unwrap();
+        let entry = (midnight - time::Duration::seconds(1)).to_offset(ahead);
+        let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+        let timestamp = entry.format(TIMESTAMP_FORMAT).unwrap();
+        fs::write(&log_file, format!("{timestamp} INFO  [Test] (Backend : test.rs: 1): message\n")).unwrap();
+
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
+
+        assert!(!log_file.exists(), "entry from before local midnight should be rotated");
+        assert!(dir.join(format!("{LOG_FILE_NAME}_{}.log.gz", days_ago_str(1))).exists());
+    }
+
     #[test]
     fn rotate_truncates_garbage_file() {
         let dir = test_dir("rotate_garbage");
         let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
         fs::write(&log_file, "no valid timestamps here\n").unwrap();
 
-        rotate_logs_in(&dir);
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
 
         assert!(log_file.exists(), "file should still exist");
         assert_eq!(fs::read_to_string(&log_file).unwrap(), "", "file should be empty");
@@ -659,7 +2407,7 @@ mod tests {
     fn rotate_noop_when_no_log_file() {
         let dir = test_dir("rotate_noop");
         // Empty dir, no log file — should not panic
-        rotate_logs_in(&dir);
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
     }
 
     #[test]
@@ -669,7 +2417,7 @@ mod tests {
         let old_archive = dir.join(format!("{LOG_FILE_NAME}_{old_date}.log"));
         fs::write(&old_archive, "old logs").unwrap();
 
-        rotate_logs_in(&dir);
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
 
         assert!(!old_archive.exists(), "archive older than 30 days should be deleted");
     }
@@ -681,11 +2429,227 @@ mod tests {
         let recent_archive = dir.join(format!("{LOG_FILE_NAME}_{recent_date}.log"));
         fs::write(&recent_archive, "recent logs").unwrap();
 
-        rotate_logs_in(&dir);
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
 
         assert!(recent_archive.exists(), "archive within 30 days should be kept");
     }
 
+    // -- stdout colours --
+
+    /// Mutex to serialize tests that change the global `colored` override.
+    static COLOUR_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn stdout_colours_enabled_on_terminal() {
+        assert!(stdout_colours_enabled(None, true));
+        assert!(stdout_colours_enabled(Some(OsStr::new("")), true));
+    }
+
+    #[test]
+    fn stdout_colours_disabled_by_no_color() {
+        assert!(!stdout_colours_enabled(Some(OsStr::new("1")), true));
+    }
+
+    #[test]
+    fn stdout_colours_disabled_without_terminal() {
+        assert!(!stdout_colours_enabled(None, false));
+    }
+
+    #[test]
+    fn coloured_level_respects_override() {
+        let _lock = COLOUR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        colored::control::set_override(true);
+        let on = coloured_level(Level::Error);
+        colored::control::set_override(false);
+        let off = coloured_level(Level::Error);
+        colored::control::unset_override();
+
+        assert!(on.contains("\x1b["), "expected ANSI escapes: {on:?}");
+        assert_eq!(off, "ERROR");
+    }
+
+    // -- format_line --
+
+    #[test]
+    fn format_line_plain_has_no_ansi_escapes() {
+        let _lock = COLOUR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        colored::control::set_override(true);
+        let coloured = format_line(
+            &format_args!("hello"),
+            &log::Record::builder().args(format_args!("hello")).level(Level::Warn).target("Test").build(),
+            true,
+            ColumnWidths::default(),
+        );
+        let plain = format_line(
+            &format_args!("hello"),
+            &log::Record::builder().args(format_args!("hello")).level(Level::Warn).target("Test").build(),
+            false,
+            ColumnWidths::default(),
+        );
+        colored::control::unset_override();
+
+        assert!(coloured.contains("\x1b["), "stdout line should be colourised: {coloured:?}");
+        assert!(!plain.contains("\x1b["), "file line must not contain ANSI escapes: {plain:?}");
+        assert!(plain.contains(" WARN  [Test                ]"), "unexpected plain line: {plain:?}");
+    }
+
+    #[test]
+    fn format_line_splits_frontend_logger_name() {
+        let line = format_line(
+            &format_args!("App{SEP}clicked"),
+            &log::Record::builder().args(format_args!("App{SEP}clicked")).level(Level::Info).build(),
+            false,
+            ColumnWidths::default(),
+        );
+        assert!(line.contains("[App                 ] (Frontend: "), "unexpected line: {line:?}");
+        assert!(line.ends_with("): clicked"), "unexpected line: {line:?}");
+    }
+
+    #[test]
+    fn format_line_appends_frontend_context() {
+        let raw = format!("App{SEP}{{ \"tab\": \"logs\", \"count\": 2 }}{SEP}clicked");
+        let line = format_line(
+            &format_args!("{raw}"),
+            &log::Record::builder().args(format_args!("{raw}")).level(Level::Info).build(),
+            false,
+            ColumnWidths::default(),
+        );
+        assert!(line.contains("[App                 ] (Frontend: "), "unexpected line: {line:?}");
+        assert!(line.ends_with(r#"): clicked {"count":2,"tab":"logs"}"#), "unexpected line: {line:?}");
+    }
+
+    #[test]
+    fn split_message_forms() {
+        let record = log::Record::builder().target("Backend").build();
+
+        let plain = split_message("hello", &record);
+        assert_eq!((plain.origin, plain.logger, plain.message), ("Backend", "Backend", "hello"));
+        assert!(plain.context.is_none());
+
+        let named = split_message("App\x1Fclicked", &record);
+        assert_eq!((named.origin, named.logger, named.message), ("Frontend", "App", "clicked"));
+        assert!(named.context.is_none());
+
+        let with_context = split_message("App\x1F{\"id\":7}\x1Fclicked", &record);
+        assert_eq!((with_context.logger, with_context.message), ("App", "clicked"));
+        assert_eq!(with_context.context.unwrap()["id"], 7);
+
+        // An empty context adds nothing, and a middle part that is not a JSON object stays in the message
+        assert!(split_message("App\x1F{}\x1Fclicked", &record).context.is_none());
+        let not_json = split_message("App\x1Fa\x1Fb", &record);
+        assert_eq!(not_json.message, "a\x1Fb");
+        assert!(not_json.context.is_none());
+    }
+
+    #[test]
+    fn repeat_collapser_summarizes_repeats() {
+        let mut repeats = RepeatCollapser::default();
+        assert!(!repeats.repeat("10:00:00", "INFO  flood"));
+        repeats.written("INFO  flood".to_string());
+        assert_eq!(repeats.summary(), None);
+
+        assert!(repeats.repeat("10:00:01", "INFO  flood"));
+        assert!(repeats.repeat("10:00:02", "INFO  flood"));
+        assert!(!repeats.repeat("10:00:03", "INFO  other"));
+        assert_eq!(repeats.summary().as_deref(), Some("10:00:02 INFO  flood (repeated 2 times)"));
+        // The summary is only written once
+        assert_eq!(repeats.summary(), None);
+    }
+
+    #[test]
+    fn repeat_collapser_single_repeat() {
+        let mut repeats = RepeatCollapser::default();
+        repeats.written("WARN  twice".to_string());
+        assert!(repeats.repeat("10:00:01", "WARN  twice"));
+        assert_eq!(repeats.summary().as_deref(), Some("10:00:01 WARN  twice (repeated 1 time)"));
+
+        repeats.written("WARN  next".to_string());
+        assert!(!repeats.repeat("10:00:02", "WARN  twice"));
+    }
+
+    #[test]
+    fn format_line_uses_configured_widths() {
+        let line = |logger: usize, file: usize| {
+            format_line(
+                &format_args!("hello"),
+                &log::Record::builder()
+                    .args(format_args!("hello"))
+                    .level(Level::Info)
+                    .target("Test")
+                    .file(Some("src/game/entitlements.rs"))
+                    .build(),
+                false,
+                ColumnWidths { logger, file },
+            )
+        };
+        let narrow = line(10, 10);
+        let wide = line(20, 20);
+
+        assert!(narrow.contains("[Test      ]"), "unexpected line: {narrow:?}");
+        assert!(wide.contains("[Test                ]"), "unexpected line: {wide:?}");
+        assert!(narrow.contains("gam...s.rs:"), "unexpected line: {narrow:?}");
+        assert!(wide.contains("game/entitlements.rs:"), "unexpected line: {wide:?}");
+        assert_eq!(wide.chars().count() - narrow.chars().count(), 20);
+    }
+
+    #[test]
+    fn column_width_parsing() {
+        assert_eq!(column_width(None, LOGGER_NAME_WIDTH), LOGGER_NAME_WIDTH);
+        assert_eq!(column_width(Some("32"), LOGGER_NAME_WIDTH), 32);
+        assert_eq!(column_width(Some(" 10 "), FILE_PATH_WIDTH), 10);
+        assert_eq!(column_width(Some("wide"), FILE_PATH_WIDTH), FILE_PATH_WIDTH);
+        assert_eq!(column_width(Some("2"), FILE_PATH_WIDTH), FILE_PATH_WIDTH);
+    }
+
+    // -- json_line --
+
+    #[test]
+    fn json_line_backend_fields() {
+        let record = log::Record::builder()
+            .args(format_args!("hello \"world\""))
+            .level(Level::Warn)
+            .target("Test")
+            .file(Some("src/game/mod.rs"))
+            .line(Some(42))
+            .build();
+        let line = json_line(&format_args!("hello \"world\""), &record);
+        assert!(!line.contains('\n'), "JSON entry must be a single line: {line:?}");
+
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["logger"], "Test");
+        assert_eq!(value["origin"], "Backend");
+        assert_eq!(value["file"], "game/mod.rs");
+        assert_eq!(value["line"], 42);
+        assert_eq!(value["message"], "hello \"world\"");
+        assert!(value["timestamp"].as_str().is_some_and(|t| t.len() >= 23));
+    }
+
+    #[test]
+    fn json_line_splits_frontend_logger_name() {
+        let line = json_line(
+            &format_args!("App{SEP}clicked"),
+            &log::Record::builder().args(format_args!("App{SEP}clicked")).level(Level::Info).build(),
+        );
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["logger"], "App");
+        assert_eq!(value["origin"], "Frontend");
+        assert_eq!(value["message"], "clicked");
+        assert!(value.get("context").is_none());
+    }
+
+    #[test]
+    fn json_line_includes_frontend_context() {
+        let raw = format!("App{SEP}{{\"tab\":\"logs\"}}{SEP}clicked");
+        let line = json_line(
+            &format_args!("{raw}"),
+            &log::Record::builder().args(format_args!("{raw}")).level(Level::Info).build(),
+        );
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["message"], "clicked");
+        assert_eq!(value["context"]["tab"], "logs");
+    }
+
     // -- fit --
 
     #[test]
@@ -719,47 +2683,69 @@ mod tests {
         assert_eq!(fit("ü", 3), "ü  ");
     }
 
-    // -- fit_path --
+    // -- fit_middle --
 
     #[test]
-    fn fit_path_exact_width() {
-        assert_eq!(fit_path("src/main.rs", 11), "src/main.rs");
+    fn fit_middle_exact_width() {
+        assert_eq!(fit_middle("src/main.rs", 11), "src/main.rs");
     }
 
     #[test]
-    fn fit_path_shorter_pads_right() {
-        assert_eq!(fit_path("lib.rs", 10), "lib.rs    ");
+    fn fit_middle_shorter_pads_right() {
+        assert_eq!(fit_middle("lib.rs", 10), "lib.rs    ");
     }
 
     #[test]
-    fn fit_path_longer_middle_truncates() {
+    fn fit_middle_longer_middle_truncates() {
         // width=15: available=12, end_len=6, start_len=6
-        let result = fit_path("src/game/entitlements.rs", 15);
+        let result = fit_middle("src/game/entitlements.rs", 15);
         assert_eq!(result.len(), 15);
         assert!(result.contains("..."), "expected '...' in '{result}'");
     }
 
     #[test]
-    fn fit_path_preserves_extension() {
+    fn fit_middle_preserves_extension() {
         // The end (filename) should survive truncation
-        let result = fit_path("some/very/deep/nested/path/file.rs", 20);
+        let result = fit_middle("some/very/deep/nested/path/file.rs", 20);
         assert!(result.ends_with(".rs"), "expected '.rs' suffix in '{result}'");
     }
 
     #[test]
-    fn fit_path_empty_string() {
-        assert_eq!(fit_path("", 10), "          ");
+    fn fit_middle_empty_string() {
+        assert_eq!(fit_middle("", 10), "          ");
     }
 
     #[test]
-    fn fit_path_multibyte_middle_truncates() {
+    fn fit_middle_multibyte_middle_truncates() {
         // "src/müll/datei.rs" = 17 chars, width=15 — must not panic
-        let result = fit_path("src/müll/datei.rs", 15);
+        let result = fit_middle("src/müll/datei.rs", 15);
         assert_eq!(result.chars().count(), 15);
         assert!(result.contains("..."), "expected '...' in '{result}'");
     }
 
-    // -- copy_truncate_rotation --
+    #[test]
+    fn fit_middle_logger_name_exact_width() {
+        assert_eq!(fit_middle("EntitlementsCheckerX", LOGGER_NAME_WIDTH), "EntitlementsCheckerX");
+    }
+
+    #[test]
+    fn fit_middle_logger_name_shorter_pads_right() {
+        assert_eq!(fit_middle("Startup", LOGGER_NAME_WIDTH), "Startup             ");
+    }
+
+    #[test]
+    fn fit_middle_logger_name_keeps_prefix() {
+        assert_eq!(fit_middle("AuthenticationService", LOGGER_NAME_WIDTH), "Authenti...onService");
+    }
+
+    #[test]
+    fn fit_middle_logger_name_multibyte() {
+        // 21 chars, more bytes — must not panic
+        let result = fit_middle("Überwach...DienstÄÖÜ", LOGGER_NAME_WIDTH);
+        assert_eq!(result, "Überwach...DienstÄÖÜ");
+    }
+
+        // -- copy_truncate_rotation --
 
     #[test]
     fn runtime_rotation_copies_and_truncates() {
@@ -768,13 +2754,39 @@ mod tests {
         let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
         fs::write(&log_file, log_line(&yesterday)).unwrap();
 
-        copy_truncate_rotation(&dir, None);
+        copy_truncate_rotation(&dir, false);
 
-        let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}.log"));
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}.log.gz"));
+        assert!(archive.exists(), "archive should exist");
+        assert_eq!(fs::read_to_string(&log_file).unwrap(), "", "log file should be truncated");
+    }
+
+    #[test]
+    fn runtime_rotation_into_fresh_nested_dir() {
+        let root = test_dir("runtime_fresh_dir");
+        let dir = root.join("Library/Logs/daystrom");
+        assert!(ensure_log_dir(&dir));
+
+        let yesterday = days_ago_str(1);
+        let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+        fs::write(&log_file, log_line(&yesterday)).unwrap();
+
+        copy_truncate_rotation(&dir, false);
+
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}.log.gz"));
         assert!(archive.exists(), "archive should exist");
         assert_eq!(fs::read_to_string(&log_file).unwrap(), "", "log file should be truncated");
     }
 
+    #[test]
+    fn ensure_log_dir_fails_below_file() {
+        let root = test_dir("ensure_log_dir_file");
+        let file = root.join("not-a-dir");
+        fs::write(&file, "").unwrap();
+
+        assert!(!ensure_log_dir(&file.join("logs")));
+    }
+
     #[test]
     fn runtime_rotation_skips_same_day() {
         let _lock = TEST_LOCK.lock().unwrap();
@@ -787,6 +2799,9 @@ mod tests {
         *ROTATION_STATE.lock().unwrap() = Some(RotationState {
             current_date: today_date(),
             log_dir: dir.clone(),
+            max_age_days: MAX_LOG_AGE_DAYS,
+            period: RotationPeriod::Daily,
+            max_size: MAX_LOG_FILE_SIZE,
         });
 
         check_runtime_rotation();
@@ -808,7 +2823,7 @@ mod tests {
         let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}.log"));
         fs::write(&archive, "existing archive content").unwrap();
 
-        copy_truncate_rotation(&dir, None);
+        copy_truncate_rotation(&dir, false);
 
         assert_eq!(
             fs::read_to_string(&archive).unwrap(),
@@ -827,7 +2842,7 @@ mod tests {
         let plugin_archive = dir.join(format!("{LOG_FILE_NAME}_{old_date}_14-30-45.log"));
         fs::write(&plugin_archive, "old plugin log").unwrap();
 
-        cleanup_old_archives(&dir, today_date());
+        cleanup_old_archives(&dir, today_date(), MAX_LOG_AGE_DAYS);
 
         assert!(!plugin_archive.exists(), "plugin-format archive older than 30 days should be deleted");
     }
@@ -845,151 +2860,327 @@ mod tests {
         let plugin_archive = dir.join(format!("{LOG_FILE_NAME}_{old_date}_14-30-45.log"));
         fs::write(&plugin_archive, "old plugin log").unwrap();
 
-        cleanup_old_archives(&dir, today_date());
+        cleanup_old_archives(&dir, today_date(), MAX_LOG_AGE_DAYS);
 
         assert!(!our_archive.exists(), "our archive older than 30 days should be deleted");
         assert!(!plugin_archive.exists(), "plugin archive older than 30 days should be deleted");
     }
 
-    // -- normalize_plugin_archives --
+    #[test]
+    fn cleanup_handles_compressed_archives() {
+        let dir = test_dir("cleanup_compressed");
+        let old_archive = dir.join(format!("{LOG_FILE_NAME}_{}.log.gz", days_ago_str(31)));
+        let recent_archive = dir.join(format!("{LOG_FILE_NAME}_{}_14-30-45.log.gz", days_ago_str(15)));
+        fs::write(&old_archive, "old").unwrap();
+        fs::write(&recent_archive, "recent").unwrap();
+
+        cleanup_old_archives(&dir, today_date(), MAX_LOG_AGE_DAYS);
+
+        assert!(!old_archive.exists(), "compressed archive older than 30 days should be deleted");
+        assert!(recent_archive.exists(), "compressed archive within 30 days should be kept");
+    }
 
     #[test]
-    fn normalize_no_plugin_files() {
-        let dir = test_dir("normalize_empty");
-        assert_eq!(normalize_plugin_archives(&dir, "2026-01-15"), None);
+    fn gzip_compression_round_trip() {
+        let dir = test_dir("compress_gzip");
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{}.log", days_ago_str(1)));
+        let content = log_line(&days_ago_str(1));
+        fs::write(&archive, &content).unwrap();
+
+        let compressed = compress_archive_with(&archive, LogCompression::Gzip).unwrap();
+
+        assert_eq!(compressed, compressed_path(&archive, LogCompression::Gzip));
+        assert!(!archive.exists(), "plain archive should be replaced");
+        let mut unpacked = String::new();
+        GzDecoder::new(fs::File::open(&compressed).unwrap()).read_to_string(&mut unpacked).unwrap();
+        assert_eq!(unpacked, content);
+        assert_eq!(read_archive(&compressed).unwrap(), content.as_bytes());
+        assert_eq!(list_archives(&dir).unwrap().len(), 1);
     }
 
     #[test]
-    fn normalize_renames_single_file() {
-        let dir = test_dir("normalize_single");
-        let date = "2026-01-15";
-        fs::write(
-            dir.join(format!("{LOG_FILE_NAME}_{date}_09-00-00.log")),
-            "log content",
-        )
-        .unwrap();
+    fn zstd_compression_round_trip() {
+        let dir = test_dir("compress_zstd");
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{}.log", days_ago_str(1)));
+        let content = log_line(&days_ago_str(1)).repeat(100);
+        fs::write(&archive, &content).unwrap();
+
+        let compressed = compress_archive_with(&archive, LogCompression::Zstd).unwrap();
+
+        assert_eq!(compressed, compressed_path(&archive, LogCompression::Zstd));
+        assert!(compressed.to_string_lossy().ends_with(".log.zst"));
+        assert!(!archive.exists(), "plain archive should be replaced");
+        assert!(fs::metadata(&compressed).unwrap().len() < content.len() as u64);
+        assert_eq!(zstd::decode_all(fs::File::open(&compressed).unwrap()).unwrap(), content.as_bytes());
+        assert_eq!(read_archive(&compressed).unwrap(), content.as_bytes());
+        assert_eq!(list_archives(&dir).unwrap().len(), 1);
+    }
 
-        let result = normalize_plugin_archives(&dir, date);
-        assert_eq!(result.as_deref(), Some("09-00-00"));
+    #[test]
+    fn no_compression_round_trip() {
+        let dir = test_dir("compress_none");
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{}.log", days_ago_str(1)));
+        let content = log_line(&days_ago_str(1));
+        fs::write(&archive, &content).unwrap();
+
+        let kept = compress_archive_with(&archive, LogCompression::None).unwrap();
+
+        assert_eq!(kept, archive);
+        assert_eq!(read_archive(&kept).unwrap(), content.as_bytes());
+        assert!(!compressed_path(&archive, LogCompression::Gzip).exists());
+        assert!(!compressed_path(&archive, LogCompression::Zstd).exists());
+        assert_eq!(list_archives(&dir).unwrap().len(), 1);
+    }
 
-        assert!(
-            dir.join(format!("{LOG_FILE_NAME}_{date}_00-00-00.log")).exists(),
-            "file should be renamed to _00-00-00"
-        );
-        assert!(
-            !dir.join(format!("{LOG_FILE_NAME}_{date}_09-00-00.log")).exists(),
-            "original file should no longer exist"
-        );
+    #[test]
+    fn cleanup_handles_zstd_archives() {
+        let dir = test_dir("cleanup_zstd");
+        let old_archive = dir.join(format!("{LOG_FILE_NAME}_{}.log.zst", days_ago_str(31)));
+        let recent_archive = dir.join(format!("{LOG_FILE_NAME}_{}_14-30-45.log.zst", days_ago_str(15)));
+        fs::write(&old_archive, "old").unwrap();
+        fs::write(&recent_archive, "recent").unwrap();
+
+        cleanup_old_archives(&dir, today_date(), MAX_LOG_AGE_DAYS);
+
+        assert!(!old_archive.exists(), "zstd archive older than 30 days should be deleted");
+        assert!(recent_archive.exists(), "zstd archive within 30 days should be kept");
+        assert!(archive_exists(&recent_archive.with_extension("")), "a zstd archive blocks its plain name");
     }
 
     #[test]
-    fn normalize_renames_chain() {
-        let dir = test_dir("normalize_chain");
-        let date = "2026-01-15";
-        for time in ["09-00-00", "13-00-00", "21-00-00"] {
-            fs::write(
-                dir.join(format!("{LOG_FILE_NAME}_{date}_{time}.log")),
-                format!("content from {time}"),
-            )
-            .unwrap();
+    fn cleanup_respects_custom_retention() {
+        let dir = test_dir("cleanup_custom_retention");
+        let old_archive = dir.join(format!("{LOG_FILE_NAME}_{}.log", days_ago_str(6)));
+        let recent_archive = dir.join(format!("{LOG_FILE_NAME}_{}.log", days_ago_str(4)));
+        fs::write(&old_archive, "old log").unwrap();
+        fs::write(&recent_archive, "recent log").unwrap();
+
+        cleanup_old_archives(&dir, today_date(), 5);
+
+        assert!(!old_archive.exists(), "archive older than 5 days should be deleted");
+        assert!(recent_archive.exists(), "archive within 5 days should be kept");
+    }
+
+    // -- log_retention_days --
+
+    #[test]
+    fn retention_days_from_env_value() {
+        assert_eq!(log_retention_days(Some("90")), 90);
+        assert_eq!(log_retention_days(Some(" 5 ")), 5);
+    }
+
+    #[test]
+    fn retention_days_falls_back_to_default() {
+        assert_eq!(log_retention_days(None), MAX_LOG_AGE_DAYS);
+        assert_eq!(log_retention_days(Some("")), MAX_LOG_AGE_DAYS);
+        assert_eq!(log_retention_days(Some("forever")), MAX_LOG_AGE_DAYS);
+        assert_eq!(log_retention_days(Some("-3")), MAX_LOG_AGE_DAYS);
+    }
+
+    #[test]
+    fn cleanup_never_deletes_todays_archives() {
+        let dir = test_dir("cleanup_keeps_today");
+        let today = days_ago_str(0);
+        let kept = [
+            dir.join(format!("{LOG_FILE_NAME}_{today}.log.gz")),
+            dir.join(format!("{LOG_FILE_NAME}_{today}_00-00-01.log")),
+        ];
+        for archive in &kept {
+            fs::write(archive, "today's log").unwrap();
         }
+        let old = dir.join(format!("{LOG_FILE_NAME}_{}.log", days_ago_str(1)));
+        fs::write(&old, "yesterday's log").unwrap();
+        let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+        fs::write(&log_file, "current log").unwrap();
 
-        let result = normalize_plugin_archives(&dir, date);
-        assert_eq!(result.as_deref(), Some("21-00-00"));
+        cleanup_old_archives(&dir, today_date(), 0);
 
-        // Verify renamed files exist with correct content
-        assert_eq!(
-            fs::read_to_string(dir.join(format!("{LOG_FILE_NAME}_{date}_00-00-00.log"))).unwrap(),
-            "content from 09-00-00"
-        );
-        assert_eq!(
-            fs::read_to_string(dir.join(format!("{LOG_FILE_NAME}_{date}_09-00-00.log"))).unwrap(),
-            "content from 13-00-00"
-        );
-        assert_eq!(
-            fs::read_to_string(dir.join(format!("{LOG_FILE_NAME}_{date}_13-00-00.log"))).unwrap(),
-            "content from 21-00-00"
-        );
-        assert!(
-            !dir.join(format!("{LOG_FILE_NAME}_{date}_21-00-00.log")).exists(),
-            "_21-00-00 should no longer exist"
-        );
+        for archive in &kept {
+            assert!(archive.exists(), "{} is dated today and must be kept", archive.display());
+        }
+        assert!(log_file.exists(), "current log file must be kept");
+        assert!(!old.exists(), "yesterday's archive is outside a zero-day window");
     }
 
     #[test]
-    fn normalize_skips_other_dates() {
-        let dir = test_dir("normalize_other_dates");
-        let target = "2026-01-15";
-        let other = "2026-01-14";
-        fs::write(
-            dir.join(format!("{LOG_FILE_NAME}_{target}_09-00-00.log")),
-            "target",
-        )
-        .unwrap();
-        fs::write(
-            dir.join(format!("{LOG_FILE_NAME}_{other}_12-00-00.log")),
-            "other",
-        )
-        .unwrap();
+    fn cleanup_oversized_removes_oldest_first() {
+        let dir = test_dir("cleanup_oversized");
+        let names: Vec<_> = [4, 3, 2, 1]
+            .iter()
+            .map(|days| format!("{LOG_FILE_NAME}_{}.log.gz", days_ago_str(*days)))
+            .collect();
+        for name in &names {
+            fs::write(dir.join(name), "x".repeat(100)).unwrap();
+        }
+        // Same day as the oldest, but with a time suffix: sorts after the date-only archive
+        let same_day = format!("{LOG_FILE_NAME}_{}_09-00-00.log", days_ago_str(4));
+        fs::write(dir.join(&same_day), "x".repeat(100)).unwrap();
 
-        normalize_plugin_archives(&dir, target);
+        cleanup_oversized_archives(&dir, 250);
 
-        assert!(
-            dir.join(format!("{LOG_FILE_NAME}_{other}_12-00-00.log")).exists(),
-            "other date's file should remain unchanged"
-        );
+        assert!(!dir.join(&names[0]).exists(), "oldest archive should be deleted");
+        assert!(!dir.join(&same_day).exists(), "second oldest archive should be deleted");
+        assert!(!dir.join(&names[1]).exists(), "third oldest archive should be deleted");
+        assert!(dir.join(&names[2]).exists(), "archive within the cap should be kept");
+        assert!(dir.join(&names[3]).exists(), "newest archive should be kept");
+    }
+
+    #[test]
+    fn cleanup_oversized_noop_under_cap() {
+        let dir = test_dir("cleanup_oversized_noop");
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{}.log", days_ago_str(2)));
+        fs::write(&archive, "x".repeat(100)).unwrap();
+        let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+        fs::write(&log_file, "x".repeat(1000)).unwrap();
+
+        cleanup_oversized_archives(&dir, 100);
+
+        assert!(archive.exists(), "archive at the cap should be kept");
+        assert!(log_file.exists(), "current log file is never an archive");
     }
 
-    // -- copy_truncate_rotation with time suffix --
+    #[test]
+    fn verify_archives_accepts_healthy_set() {
+        let dir = test_dir("verify_archives_healthy");
+        let date = days_ago_str(1);
+        for name in [format!("{date}.log.gz"), format!("{date}_09-00-00.log"), format!("{date}_12-00-00.log.gz")] {
+            fs::write(dir.join(format!("{LOG_FILE_NAME}_{name}")), log_line(&date)).unwrap();
+        }
+        fs::write(dir.join(format!("{LOG_FILE_NAME}.log")), log_line(&date)).unwrap();
+
+        assert!(verify_archives(&dir).is_empty());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 4, "nothing should be removed");
+    }
+
+    #[test]
+    fn verify_archives_reports_colliding_set() {
+        let dir = test_dir("verify_archives_colliding");
+        let date = days_ago_str(1);
+        let stem = format!("{LOG_FILE_NAME}_{date}_09-00-00");
+        fs::write(dir.join(format!("{stem}.log")), log_line(&date)).unwrap();
+        fs::write(dir.join(format!("{stem}.log.gz")), "not the same log").unwrap();
+        fs::create_dir_all(dir.join("old")).unwrap();
+        fs::write(dir.join("old").join(format!("{LOG_FILE_NAME}_{date}.log")), log_line(&date)).unwrap();
+        fs::write(dir.join(format!("{LOG_FILE_NAME}_{date}.log")), log_line(&date)).unwrap();
+
+        let mut collisions = verify_archives(&dir);
+        collisions.iter_mut().for_each(|names| names.sort());
+        collisions.sort();
+        let in_old = Path::new("old").join(format!("{LOG_FILE_NAME}_{date}.log")).display().to_string();
+        let same_day = vec![in_old, format!("{LOG_FILE_NAME}_{date}.log")];
+        assert_eq!(collisions, [same_day, vec![format!("{stem}.log"), format!("{stem}.log.gz")]]);
+        assert!(dir.join(format!("{stem}.log")).exists(), "differing duplicates must be kept");
+    }
+
+    #[test]
+    fn verify_archives_repairs_interrupted_compression() {
+        let dir = test_dir("verify_archives_interrupted");
+        let date = days_ago_str(1);
+        let plain = dir.join(format!("{LOG_FILE_NAME}_{date}_09-00-00.log"));
+        fs::write(&plain, log_line(&date)).unwrap();
+        fs::copy(&plain, dir.join("copy.log")).unwrap();
+        compress_archive(&dir.join("copy.log")).unwrap();
+        fs::rename(dir.join("copy.log.gz"), compressed_path(&plain, LogCompression::Gzip)).unwrap();
+
+        assert!(verify_archives(&dir).is_empty());
+        assert!(!plain.exists(), "plain copy of a complete compression should be removed");
+        assert!(compressed_path(&plain, LogCompression::Gzip).exists());
+    }
+
+    #[test]
+    fn verify_archives_repairs_interrupted_zstd_compression() {
+        let dir = test_dir("verify_archives_interrupted_zstd");
+        let date = days_ago_str(1);
+        let plain = dir.join(format!("{LOG_FILE_NAME}_{date}_09-00-00.log"));
+        fs::write(&plain, log_line(&date)).unwrap();
+        fs::copy(&plain, dir.join("copy.log")).unwrap();
+        let compressed = compress_archive_with(&dir.join("copy.log"), LogCompression::Zstd).unwrap();
+        fs::rename(compressed, compressed_path(&plain, LogCompression::Zstd)).unwrap();
+
+        assert!(verify_archives(&dir).is_empty());
+        assert!(!plain.exists(), "plain copy of a complete compression should be removed");
+        assert!(compressed_path(&plain, LogCompression::Zstd).exists());
+    }
+
+    // -- size rotation --
+
+    #[test]
+    fn log_max_size_parsing() {
+        assert_eq!(log_max_size(None), MAX_LOG_FILE_SIZE);
+        assert_eq!(log_max_size(Some("500")), 500_000);
+        assert_eq!(log_max_size(Some(" 2000 ")), 2_000_000);
+        assert_eq!(log_max_size(Some("0")), MAX_LOG_FILE_SIZE);
+        assert_eq!(log_max_size(Some("1MB")), MAX_LOG_FILE_SIZE);
+    }
 
     #[test]
-    fn copy_truncate_with_time_suffix() {
+    fn copy_truncate_size_rotation_uses_first_entry_time() {
         let dir = test_dir("copy_truncate_time_suffix");
         let yesterday = days_ago_str(1);
         let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
         fs::write(&log_file, log_line(&yesterday)).unwrap();
 
-        copy_truncate_rotation(&dir, Some("21-00-00"));
+        copy_truncate_rotation(&dir, true);
 
-        let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}_21-00-00.log"));
-        assert!(archive.exists(), "archive should include time suffix");
+        let archive = dir.join(format!("{LOG_FILE_NAME}_{yesterday}_14-30-45.log.gz"));
+        assert!(archive.exists(), "archive should include the first entry's time");
         assert_eq!(fs::read_to_string(&log_file).unwrap(), "", "log file should be truncated");
     }
 
-    // -- rotate_logs_in with plugin archives --
-
     #[test]
-    fn rotate_startup_normalizes_archives() {
-        let dir = test_dir("startup_normalize");
+    fn rotate_startup_suffixes_after_size_rotations() {
+        let dir = test_dir("startup_after_size_rotation");
         let yesterday = days_ago_str(1);
         let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
         fs::write(&log_file, log_line(&yesterday)).unwrap();
+        let earlier = dir.join(format!("{LOG_FILE_NAME}_{yesterday}_09-00-00.log.gz"));
+        fs::write(&earlier, "earlier segment").unwrap();
 
-        // Create plugin-rotated files for yesterday
-        for time in ["09-00-00", "13-00-00"] {
-            fs::write(
-                dir.join(format!("{LOG_FILE_NAME}_{yesterday}_{time}.log")),
-                format!("plugin content {time}"),
-            )
-            .unwrap();
-        }
-
-        rotate_logs_in(&dir);
+        rotate_logs_in(&dir, MAX_LOG_AGE_DAYS, RotationPeriod::Daily);
 
-        // Plugin files should be normalized
-        assert!(
-            dir.join(format!("{LOG_FILE_NAME}_{yesterday}_00-00-00.log")).exists(),
-            "first plugin file should be renamed to _00-00-00"
-        );
         assert!(
-            dir.join(format!("{LOG_FILE_NAME}_{yesterday}_09-00-00.log")).exists(),
-            "second plugin file should be renamed to _09-00-00"
-        );
-        // Main log file should be archived with the last original time
-        assert!(
-            dir.join(format!("{LOG_FILE_NAME}_{yesterday}_13-00-00.log")).exists(),
-            "main log should be archived with last plugin time"
+            dir.join(format!("{LOG_FILE_NAME}_{yesterday}_14-30-45.log.gz")).exists(),
+            "last segment should be archived with its first entry's time"
         );
+        assert_eq!(fs::read_to_string(&earlier).unwrap(), "earlier segment");
         assert!(!log_file.exists(), "original log file should be gone");
     }
+
+    #[test]
+    fn runtime_rotation_on_size() {
+        let _lock = TEST_LOCK.lock().unwrap();
+        let dir = test_dir("runtime_size");
+        let today = today_str();
+        let log_file = dir.join(format!("{LOG_FILE_NAME}.log"));
+        fs::write(&log_file, log_line(&today).repeat(3)).unwrap();
+        let json_file = dir.join(format!("{JSON_LOG_FILE_NAME}.log"));
+        fs::write(&json_file, "{}\n".repeat(100)).unwrap();
+
+        *ROTATION_STATE.lock().unwrap() = Some(RotationState {
+            current_date: today_date(),
+            log_dir: dir.clone(),
+            max_age_days: MAX_LOG_AGE_DAYS,
+            period: RotationPeriod::Daily,
+            max_size: 100,
+        });
+
+        check_runtime_rotation();
+
+        // Clean up global state
+        *ROTATION_STATE.lock().unwrap() = None;
+
+        assert!(dir.join(format!("{LOG_FILE_NAME}_{today}_14-30-45.log.gz")).exists(), "archive should exist");
+        assert_eq!(fs::read_to_string(&log_file).unwrap(), "", "log file should be truncated");
+        assert_eq!(fs::read_to_string(&json_file).unwrap(), "", "JSON log should be truncated");
+    }
+
+    #[test]
+    fn push_line_keeps_last_lines() {
+        let buffer = Mutex::new(VecDeque::new());
+        for i in 0..5 {
+            push_line(&buffer, format!("line {i}"), 3);
+        }
+        let lines: Vec<String> = buffer.into_inner().unwrap().into();
+        assert_eq!(lines, ["line 2", "line 3", "line 4"]);
+    }
 }
@@ -1,5 +1,9 @@
+#[cfg(target_os = "macos")]
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
+#[cfg(target_os = "macos")]
+use std::time::SystemTime;
 use std::time::{Duration, Instant};
 
 use tauri::Emitter;
@@ -16,15 +20,36 @@ const POLL_INTERVAL: Duration = Duration::from_secs(2);
 /// Interval for re-checking the Scopely update API while the launcher is open.
 const API_RECHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
 
+/// Event emitted when the game process starts or stops.
+///
+/// The payload is a plain boolean (`true` = running), so the frontend can subscribe with `listen<boolean>(...)`.
+pub const GAME_RUNNING_CHANGED: &str = "game-running-changed";
+
+/// Event emitted when the game executable changed on disk (e.g. by a game update) and lost the entitlements needed
+/// for mod injection (macOS only).
+///
+/// The payload is the list of missing entitlement names, without the `com.apple.security.` prefix.
+#[cfg(target_os = "macos")]
+pub const ENTITLEMENTS_LOST: &str = "entitlements-lost";
+
 /// Flag indicating whether a monitor thread is currently active.
 static ACTIVE: AtomicBool = AtomicBool::new(false);
 
-/// Start the permanent background process monitor.
+/// Start the background process monitor.
 ///
 /// Spawns a thread that polls game and launcher process status every 2 seconds and pushes state changes to the frontend
-/// via Tauri events. Runs for the entire lifetime of the application.
+/// via Tauri events, for the rest of the application's lifetime. If no game is detected, returns without starting;
+/// the settings watcher calls this again once the launcher records a new install.
 /// Safe to call multiple times; subsequent calls are no-ops.
 pub fn start(app: tauri::AppHandle) {
+    if ACTIVE.load(Ordering::SeqCst) {
+        log_debug!("Monitor already active");
+        return;
+    }
+    if game::detect().is_none() {
+        log_debug!("No game detected, not starting the process monitor");
+        return;
+    }
     if ACTIVE.swap(true, Ordering::SeqCst) {
         log_debug!("Monitor already active");
         return;
@@ -38,17 +63,21 @@ pub fn start(app: tauri::AppHandle) {
 
 /// Main monitoring loop.
 ///
-/// Checks process status every [`POLL_INTERVAL`] seconds. Emits `process-status` events on state changes, `game-status`
-/// events after a process exits (full refresh), and `update-check` events for periodic API rechecks while the launcher
-/// is open. Runs indefinitely.
+/// Checks process status every [`POLL_INTERVAL`] seconds. Emits `process-status` events on state changes,
+/// [`GAME_RUNNING_CHANGED`] events when the game starts or stops, `game-status` events after a process exits (full
+/// refresh), and `update-check` events for periodic API rechecks while the launcher is open. On macOS, also emits
+/// [`ENTITLEMENTS_LOST`] when the game executable was replaced without the required entitlements. Runs indefinitely.
 fn run_loop(app: tauri::AppHandle) {
     let mut prev_game = false;
     let mut prev_launcher = false;
     let mut last_api_check = Instant::now();
+    #[cfg(target_os = "macos")]
+    let mut last_executable: Option<(PathBuf, SystemTime)> = None;
 
     loop {
         thread::sleep(POLL_INTERVAL);
 
+        // Same check as `GameStatus.game_running`, on whichever install is selected now
         let game = game::is_game_running();
         let launcher = game::is_launcher_running();
 
@@ -60,6 +89,11 @@ fn run_loop(app: tauri::AppHandle) {
             });
         }
 
+        if game != prev_game {
+            log_debug!("Game running state changed: {game}");
+            let _ = app.emit(GAME_RUNNING_CHANGED, game);
+        }
+
         // Game just exited: push full status refresh
         if prev_game && !game {
             log_debug!("Game process ended, refreshing status");
@@ -67,9 +101,10 @@ fn run_loop(app: tauri::AppHandle) {
             let _ = app.emit("game-status", status);
         }
 
-        // Launcher just exited: push full status refresh
+        // Launcher just exited: it may have installed an update, so rescan and push full status refresh
         if prev_launcher && !launcher {
             log_debug!("Launcher process ended, refreshing status");
+            game::invalidate_detection();
             let status = commands::get_game_status(app.clone());
             let _ = app.emit("game-status", status);
         }
@@ -77,6 +112,7 @@ fn run_loop(app: tauri::AppHandle) {
         // Periodic API recheck while the launcher is open
         if launcher && last_api_check.elapsed() >= API_RECHECK_INTERVAL {
             log_debug!("Periodic update check");
+            game::invalidate_detection();
             match commands::check_for_update() {
                 Ok(check) => { let _ = app.emit("update-check", check); }
                 Err(_) => { let _ = app.emit("update-check-failed", ()); }
@@ -84,7 +120,38 @@ fn run_loop(app: tauri::AppHandle) {
             last_api_check = Instant::now();
         }
 
+        #[cfg(target_os = "macos")]
+        check_entitlements_lost(&app, &mut last_executable);
+
         prev_game = game;
         prev_launcher = launcher;
     }
 }
+
+/// Re-check the entitlements when the game executable's modification time changed since the last poll, and emit
+/// [`ENTITLEMENTS_LOST`] if any are missing.
+///
+/// Game updates re-download and re-sign the executable, which wipes the patched entitlements. Our own patching
+/// preserves the modification time, so it does not trigger this. The first poll only records the current state.
+#[cfg(target_os = "macos")]
+fn check_entitlements_lost(app: &tauri::AppHandle, last: &mut Option<(PathBuf, SystemTime)>) {
+    let Some(info) = game::detect() else { return };
+    let Ok(mtime) = std::fs::metadata(&info.executable).and_then(|meta| meta.modified()) else { return };
+
+    let changed = matches!(last, Some((path, prev)) if *path == info.executable && *prev != mtime);
+    *last = Some((info.executable.clone(), mtime));
+    if !changed {
+        return;
+    }
+
+    log_info!("Game executable changed on disk, re-checking entitlements");
+    match game::entitlements::check(&info.executable) {
+        Ok(status) if status.all_granted() => log_debug!("Entitlements still granted"),
+        Ok(status) => {
+            let names: Vec<String> = status.missing_short().into_iter().map(String::from).collect();
+            log_warn!("Entitlements lost after the executable changed: {}", names.join(", "));
+            let _ = app.emit(ENTITLEMENTS_LOST, names);
+        }
+        Err(e) => log_warn!("Could not re-check entitlements: {e}"),
+    }
+}
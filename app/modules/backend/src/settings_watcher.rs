@@ -0,0 +1,154 @@
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::Emitter;
+
+use crate::commands;
+use crate::game;
+use crate::monitor;
+use crate::use_log;
+
+use_log!("SettingsWatcher");
+
+/// Event emitted with the refreshed `GameStatus` after the launcher settings file changed.
+pub const GAME_STATUS_CHANGED: &str = "game-status-changed";
+
+/// The running file-system watcher, if any. Dropping it stops watching.
+static WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+
+/// Modification time and size of a file, or `None` if it does not exist.
+type Fingerprint = Option<(SystemTime, u64)>;
+
+/// Start watching the Scopely launcher settings INI for changes.
+///
+/// When the player installs or moves STFC while Project Daystrom is open, the launcher rewrites its settings. A
+/// file-system watcher on the settings directory picks up changes to the INI and, if its [`fingerprint`] changed,
+/// drops the cached detection and emits [`GAME_STATUS_CHANGED`] with a fresh status, without starting an update
+/// check. Once a game is found, it also starts the process monitor, which does not run without one. The directory
+/// is watched rather than the file, so a rewrite via a temporary file and rename is seen too.
+/// A no-op if the settings file's directory does not exist, or if the watcher is already running.
+pub fn start(app: tauri::AppHandle) {
+    let Some(path) = game::launcher_settings_path() else { return };
+    let Some(dir) = path.parent().filter(|dir| dir.is_dir()).map(Path::to_path_buf) else {
+        log_debug!("Launcher settings directory does not exist, not watching {}", path.display());
+        return;
+    };
+    let Some(file_name) = path.file_name().map(OsStr::to_os_string) else { return };
+
+    let mut watcher = WATCHER.lock().unwrap_or_else(|e| e.into_inner());
+    if watcher.is_some() {
+        log_debug!("Settings watcher already active");
+        return;
+    }
+
+    let mut last = fingerprint(&path);
+    let handler = move |result: notify::Result<Event>| {
+        let event = match result {
+            Ok(event) => event,
+            Err(e) => {
+                log_warn!("Settings watcher error: {e}");
+                return;
+            }
+        };
+        if !is_settings_change(&event, &file_name) {
+            return;
+        }
+        // One save usually raises several events; only act once the content actually changed
+        let current = fingerprint(&path);
+        if current == last {
+            return;
+        }
+        last = current;
+        log_info!("Launcher settings changed, refreshing game status");
+        game::invalidate_detection();
+        let status = commands::game_status_only(&app);
+        if status.installed {
+            // The monitor does not run while no game is installed; a no-op if it already does
+            monitor::start(app.clone());
+        }
+        let _ = app.emit(GAME_STATUS_CHANGED, status);
+    };
+
+    let mut notify_watcher = match notify::recommended_watcher(handler) {
+        Ok(w) => w,
+        Err(e) => {
+            log_warn!("Could not create the settings watcher: {e}");
+            return;
+        }
+    };
+    if let Err(e) = notify_watcher.watch(&dir, RecursiveMode::NonRecursive) {
+        log_warn!("Could not watch {}: {e}", dir.display());
+        return;
+    }
+    log_debug!("Watching launcher settings in {}", dir.display());
+    *watcher = Some(notify_watcher);
+}
+
+/// Stop watching. Called when the app exits; a no-op if the watcher never started.
+pub fn stop() {
+    let watcher = WATCHER.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(watcher) = watcher {
+        drop(watcher);
+        log_debug!("Settings watcher stopped");
+    }
+}
+
+/// Whether `event` creates, modifies, or removes the file named `file_name`; access events are ignored.
+fn is_settings_change(event: &Event, file_name: &OsStr) -> bool {
+    let kind = event.kind;
+    (kind.is_create() || kind.is_modify() || kind.is_remove())
+        && event.paths.iter().any(|path| path.file_name() == Some(file_name))
+}
+
+/// Read the modification time and size of `path`, so rewrites within the same second are still noticed.
+fn fingerprint(path: &Path) -> Fingerprint {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+// ---- Tests ----
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, EventKind, ModifyKind};
+
+    #[test]
+    fn fingerprint_tracks_file_changes() {
+        let dir = std::env::temp_dir().join("daystrom_test_settings_watcher");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let ini = dir.join("launcher_settings.ini");
+
+        assert_eq!(fingerprint(&ini), None);
+
+        fs::write(&ini, "[General]\n").unwrap();
+        let created = fingerprint(&ini);
+        assert!(created.is_some());
+
+        fs::write(&ini, "[General]\n152033..GAME_PATH=C:/Games/STFC/\n").unwrap();
+        assert_ne!(fingerprint(&ini), created);
+
+        fs::remove_file(&ini).unwrap();
+        assert_eq!(fingerprint(&ini), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn settings_change_filters_by_file_and_kind() {
+        let name = OsStr::new("launcher_settings.ini");
+        let ini = Path::new("/Scopely/launcher_settings.ini");
+        let event = |kind, path: &Path| Event::new(kind).add_path(path.to_path_buf());
+
+        assert!(is_settings_change(&event(EventKind::Modify(ModifyKind::Any), ini), name));
+        assert!(is_settings_change(&event(EventKind::Create(CreateKind::File), ini), name));
+        assert!(!is_settings_change(&event(EventKind::Access(AccessKind::Any), ini), name));
+        let other = Path::new("/Scopely/launcher.log");
+        assert!(!is_settings_change(&event(EventKind::Modify(ModifyKind::Any), other), name));
+    }
+}
@@ -1,5 +1,5 @@
 import {execSync} from 'node:child_process';
-import {cpSync, existsSync, mkdirSync} from 'node:fs';
+import {cpSync, existsSync, mkdirSync, readFileSync, writeFileSync} from 'node:fs';
 import {basename, join, resolve} from 'node:path';
 import process from 'node:process';
 import {configureLogging, useLog} from '@mburchard/bit-log';
@@ -25,6 +25,7 @@ const ROOT = resolve(import.meta.dirname, '..');
 const APP_DIR = join(ROOT, 'app');
 const MOD_DIR = join(ROOT, 'stfc-mod');
 const MOD_OUTPUT_DIR = join(APP_DIR, 'resources', 'mod');
+const MOD_VERSION_HEADER = join(MOD_DIR, 'mods', 'src', 'version.h');
 const MANIFEST_PATH = join(APP_DIR, 'modules', 'backend', 'Cargo.toml');
 const TS_RS_EXPORT_DIR = join(APP_DIR, 'modules', 'app', 'src', 'generated');
 const TAURI_APP_PATH = join(APP_DIR, 'modules', 'backend');
@@ -214,6 +215,26 @@ function buildMod(): void {
   const dest = join(MOD_OUTPUT_DIR, basename(src));
   cpSync(src, dest);
   log.info(`Copied ${dest}`);
+
+  const version = readModVersion();
+  writeFileSync(join(MOD_OUTPUT_DIR, 'version.txt'), `${version}\n`);
+  log.info(`Mod version ${version}`);
+}
+
+/**
+ * Read the mod version from the mod's version.h, the same way its xmake build does.
+ * @returns the version as "major.minor.revision.patch"
+ */
+function readModVersion(): string {
+  const header = readFileSync(MOD_VERSION_HEADER, 'utf8');
+  const parts = ['MAJOR', 'MINOR', 'REVISION', 'PATCH'].map((part) => {
+    const match = header.match(new RegExp(`#define VERSION_${part}\\s+(\\d+)`));
+    if (!match) {
+      throw new Error(`VERSION_${part} not found in ${MOD_VERSION_HEADER}`);
+    }
+    return match[1];
+  });
+  return parts.join('.');
 }
 
 /**